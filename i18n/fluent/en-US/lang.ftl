@@ -3,10 +3,274 @@ root-path = # Root Path: { $root_path }
 read-directory-failed = Read directory { $dir_path } failed.
 read-directory-entry-failed = Read directory entry failed.
 get-file-name-failed = Get file name failed.
-osstring-to-string-failed = Converts OsString to String failed.
 get-metadata-error = Get file's metadata error at { $dir_path }.
 generate-bash-like-cmds-helper = Generating bash-like commands.
 directly-run-helper = Directly run cargo as a subproecss.
 dry-run-helper = Dry run and output the actions.
-pathbuf-to-str-failed = Converts PathBuf to String failed.
 start-cargo-subcommand-failed = Start `Cargo { $subcommand }` failed.
+read-file-failed = Read file { $file_path } failed.
+parse-toml-failed = Parse TOML file { $file_path } failed.
+inventory-about = List the dependencies of every discovered project, with the versions in use and the projects using them.
+inventory-format-helper = Output format of the inventory.
+inventory-multi-major-only-helper = Only list the crates in use at several major versions.
+inventory-multi-major = Crate `{ $crate_name }` is used at several major versions: { $series }.
+audit-about = Check the `Cargo.lock` of every discovered project against a local RustSec advisory-db checkout.
+audit-db-helper = Path of the advisory-db checkout, `~/.cargo/advisory-db` by default.
+audit-no-yanked-helper = Don't check the locked versions against the yanked flags in the local registry index cache.
+audit-deny-warnings-helper = Exit with failure on informational advisories and yanked crates as well.
+audit-no-default-db = Can't locate the home directory, pass the advisory-db path with `--db`.
+audit-skip-advisory = Skipped the advisory { $file_path } which can't be parsed.
+audit-vulnerability = { $advisory_id } { $crate_name } { $version }: { $title } (patched: { $patched })
+audit-informational = { $advisory_id } [{ $kind }] { $crate_name } { $version }: { $title }
+audit-yanked = yanked: { $crate_name } { $version }
+audit-summary = Found { $vulnerabilities ->
+        [one] one vulnerability
+       *[other] { $vulnerabilities } vulnerabilities
+    } and { $warnings ->
+        [one] one warning
+       *[other] { $warnings } warnings
+    } in { $projects ->
+        [one] one project
+       *[other] { $projects } projects
+    }.
+interactive-helper = Pick the projects to act on from the discovered ones before running.
+selection-item = { $project }  ({ BYTES($bytes) }, modified { $age })
+selection-checklist-prompt = Select the projects to act on (space to toggle, enter to confirm)
+selection-numbered-prompt = Select the projects by number (e.g. `1 3 5-7`, `all`), empty for none:
+selection-invalid-answer = Can't understand `{ $answer }`, use the numbers listed above.
+age-unknown = at an unknown time
+age-minutes = { $minutes ->
+        [one] a minute ago
+       *[other] { $minutes } minutes ago
+    }
+age-hours = { $hours ->
+        [one] an hour ago
+       *[other] { $hours } hours ago
+    }
+age-days = { $days ->
+        [one] a day ago
+       *[other] { $days } days ago
+    }
+jobs-helper = Run up to this many projects at the same time when running as subprocesses, a project starts after its path dependencies finished.
+git-dirty-only-helper = Only act on the projects with modified, staged or untracked files in their git repository.
+git-clean-only-helper = Only act on the projects without any change in their git repository.
+skip-untracked-repos-helper = Skip the projects which aren't in a git repository or whose manifest isn't tracked.
+changed-since-helper = Only act on the projects with files changed since the given commit of the enclosing repository.
+start-git-failed = Start `git` at { $dir_path } failed.
+git-commit-helper = After a successful `update`, commit the changed `Cargo.lock` in the git repositories which had no uncommitted change before the run.
+git-commit-skip-dirty = Not committing in { $repo }, it had uncommitted changes before the run.
+git-commit-nothing = Nothing to commit at { $project }, `Cargo.lock` wouldn't change.
+git-commit-new-lock = A new `Cargo.lock` would be created at { $project } and committed.
+git-commit-done = Committed the updated `Cargo.lock` of { $project } in { $repo }.
+toolchain-helper = Run `cargo +TOOLCHAIN` in every project, repeat it to run the action once per listed toolchain.
+toolchain-overridden = toolchain { $toolchain }, overriding { $file_toolchain } of { $file_path }
+toolchain-explicit = toolchain { $toolchain }
+toolchain-from-file = toolchain { $toolchain } from { $file_path }
+toolchain-default = the default toolchain
+run-toolchain = { $project }: { $toolchain }
+env-helper = Set an environment variable for cargo, like `CARGO_TARGET_DIR=/tmp/t`, can be repeated.
+env-file-helper = Read the environment variables for cargo from a file of `KEY=VALUE` lines.
+cargo-bin-helper = Run this program instead of `cargo`, like `cross` or a wrapper script.
+env-pair-invalid = `{ $pair }` isn't in the form of `KEY=VALUE`.
+env-name-invalid = `{ $name }` isn't a variable name, made of the letters, the digits and `_`, not starting with a digit.
+makefile-helper = Generate a Makefile with a phony target per project and an `all` target.
+ninja-helper = Generate a `build.ninja` with a target per project and an `all` target.
+list-helper = Only print the discovered project directories, one per line, without any header.
+print0-helper = Delimit the listed paths with NUL instead of newline, for `xargs -0`.
+manifest-paths-helper = List the paths of the `Cargo.toml` files instead of the directories.
+from-stdin-helper = Read the project directories from stdin, one per line or NUL delimited, instead of walking the root.
+not-a-cargo-project = Skipped `{ $path }`, it isn't a directory with a `Cargo.toml`.
+report-helper = Write a report of the runs: JUnit XML for the CI servers, a Markdown table or an HTML page. Needs `--gt run-as-subprocess`.
+report-output-helper = Write the report into the file instead of stdout.
+report-needs-subprocess = No report is written, only the runs as subprocesses have results to report.
+write-file-failed = Write the file `{ $file_path }` failed.
+report-title = Cargo runs
+report-summary = { $total ->
+        [one] { $total } run
+       *[other] { $total } runs
+    }, { $failures } failed.
+report-project = Project
+report-action = Action
+report-toolchain = Toolchain
+report-status = Status
+report-duration = Duration
+report-changes = Changes
+report-passed = passed
+report-failed = failed ({ $reason })
+report-reclaimed = reclaimed { BYTES($bytes) }
+run-summary = { $total ->
+        [one] { $total } run
+       *[other] { $total } runs
+    }, { $failures ->
+        [0] none failed
+       *[other] { $failures } failed
+    }.
+run-reclaimed = Reclaimed { BYTES($bytes) } in total.
+report-failures = Failures
+verbose-helper = Show where an error is raised and its backtrace.
+lang-helper = The language of the messages, like `zh_TW`, instead of the system one. `en-XA` is a pseudo-locale made of accented English, to find the text which isn't translated.
+error-caused-by = caused by
+error-hint = hint
+error-location = at
+error-backtrace = backtrace
+error-current-dir = Reading the current directory failed.
+error-process-exit = The cargo process exited with code { $code }.
+error-process-killed = The cargo process was killed by a signal.
+error-language-files-not-found = Can't find the language files at `{ $path }`.
+error-language-negotiated = No installed language matches `{ $lang }`.
+error-advisory-db-not-found = Can't find an advisory database at `{ $path }`.
+error-std-io = Reading stdin or writing stdout failed.
+error-selection-prompt = The selection prompt failed.
+error-dependency-cycle = The path dependencies form a cycle: { $cycle }.
+error-git-command = `git { $args }` failed in `{ $dir_path }`.
+error-env-file-line = Line { $line_number } of `{ $file_path }` isn't a `KEY=VALUE` pair: { $line }
+error-serialize-json = Serializing the JSON output failed.
+hint-canonicalizing = Check that the path exists and is readable.
+hint-report-bug = This is a bug, please report it with the output of `--verbose`.
+hint-read-dir = Check the permissions of the directory, or whether it's removed during the run.
+hint-path-file-name = Pass a path which doesn't end with `..`.
+hint-current-dir = The current directory may have been removed, change to an existing one.
+hint-process-exit = Read the output of cargo above.
+hint-language-files = Put the `i18n` directory into `$XDG_DATA_HOME/rust_recursive_action` (`~/.local/share/rust_recursive_action` by default), or set `RUST_RECURSIVELY_ACTION_PATH` to the directory holding it. `--print-lang-search-path` lists the places looked.
+hint-language-negotiated = Set `LANG` to one of the installed languages, like `en-US`.
+hint-read-file = Check that the file exists and is readable.
+hint-write-file = Check that the directory of the file exists and is writable.
+hint-parse-toml = Fix the TOML syntax at the position above.
+hint-advisory-db = Clone it with `git clone https://github.com/rustsec/advisory-db ~/.cargo/advisory-db`, or pass its path with `--db`.
+hint-std-io = The other end of the pipe may have been closed.
+hint-selection-prompt = Run in a terminal, or pass the projects with `--from-stdin`.
+hint-dependency-cycle = Remove one of the path dependencies in the cycle.
+hint-start-git = Install git, or make sure it's in `PATH`.
+hint-git-command = Run the git command in the directory to see the whole error.
+hint-env-file-line = Write the line as `KEY=VALUE` with a name made of the letters, the digits and `_`, or comment it out with `#`.
+hint-start-cargo = Install cargo, or pass the program to run with `--cargo-bin`.
+hint-metadata = Fix or remove the entry, a broken symlink or a missing permission causes this.
+error-language-io = Reading the language files at `{ $path }` failed, the built-in English messages are used.
+error-parse-fluent = The language file `{ $file_path }` has errors, the built-in English messages are used: { $errors }
+error-parse-language-id = `{ $lang }` isn't a language identifier like `en-US`.
+hint-parse-fluent = Fix the syntax of the file, or reinstall the `i18n` directory.
+about = A tool to clean/update the cargo projects, recursively from root directory to leaf, and avoid directories: `src`, `.*`, `target`
+target-dir-helper = The directory to start the search from, the current directory by default.
+generating-type-helper = What to do with the discovered projects: print the commands, run them, or write a build file.
+generating-subcommand-helper = The cargo subcommand to run in every project.
+subcommand-clean-helper = `cargo clean`, remove the build artifacts.
+subcommand-update-helper = `cargo update`, update the dependencies in `Cargo.lock`.
+subcommand-build-helper = `cargo build`, compile the project.
+subcommand-check-helper = `cargo check`, check the project for errors without building it.
+subcommand-test-helper = `cargo test`, run the tests.
+subcommand-clippy-helper = `cargo clippy`, run the lints.
+report-junit-helper = JUnit XML, read by most CI servers.
+report-markdown-helper = A Markdown table, to paste into an issue.
+report-html-helper = A standalone HTML page.
+inventory-csv-helper = Comma separated values, a row per crate version and project.
+inventory-json-helper = A JSON document.
+help-helper = Print help
+version-helper = Print version
+help-subcommand-about = Print this message or the help of the given subcommand(s)
+help-usage-heading = Usage:
+help-arguments-heading = Arguments
+help-options-heading = Options
+help-commands-heading = Commands
+help-command-value-name = COMMAND
+help-possible-values-heading = Possible values
+help-default-value = default: { $value }
+clap-unknown-argument = unexpected argument '{ $arg }' found
+clap-invalid-value = invalid value '{ $value }' for '{ $arg }'
+clap-value-required = a value is required for '{ $arg }' but none was supplied
+clap-value-validation = invalid value '{ $value }' for '{ $arg }': { $reason }
+clap-invalid-subcommand = unrecognized subcommand '{ $subcommand }'
+clap-argument-conflict = the argument '{ $arg }' cannot be used with '{ $other }'
+clap-missing-required = the following required arguments were not provided: { $args }
+clap-similar-argument = a similar argument exists: '{ $arg }'
+clap-similar-value = a similar value exists: '{ $value }'
+clap-similar-subcommand = a similar subcommand exists: '{ $subcommand }'
+clap-pass-as-value = to pass '{ $arg }' as a value, use '-- { $arg }'
+clap-possible-values = possible values: { $values }
+clap-more-information = For more information, try '--help'.
+print-lang-search-path-helper = Print every place looked for the language files, and why each one isn't used.
+lang-search-entry = { $index }. { $path } ({ $origin }): { $status }
+lang-search-origin-cwd = the current directory
+lang-search-origin-env = `{ $var }`
+lang-search-origin-default = the default of `{ $var }`, which isn't set
+lang-search-origin-default-or-relative = the default of `{ $var }`, which isn't set or is relative
+lang-search-origin-exe = the `share` directory next to the executable's one
+lang-search-used = used
+lang-search-shadowed = found, but an earlier one is used
+lang-search-not-set = not set
+lang-search-relative = ignored, the relative paths aren't allowed
+lang-search-missing = doesn't exist
+lang-search-not-a-directory = isn't a directory
+lang-search-builtin = None is found, the built-in English messages are used.
+install-data-about = Install or update the language files, and the shell completions and the man page when asked, in the data directory.
+install-data-prefix-helper = Install under `PREFIX/share`, like `/usr/local`, instead of `$XDG_DATA_HOME` (`~/.local/share`).
+install-data-uninstall-helper = Remove the installed files instead, the completions and the man page included.
+install-data-completions-helper = Install the completions of bash, zsh and fish too.
+install-data-man-helper = Install the man page too.
+install-data-installed = Installed `{ $path }`.
+install-data-unchanged = `{ $path }` is up to date.
+install-data-removed = Removed `{ $path }`.
+install-data-nothing-removed = Nothing is installed under `{ $path }`.
+install-data-not-searched = The language files at `{ $path }` aren't looked for by this program, see `--print-lang-search-path`.
+completions-about = Print the completion script of a shell, in the language of the help.
+completions-shell-helper = The shell the script is written for.
+man-about = Print the man page in roff, in the language of the help.
+man-section-name = NAME
+man-section-synopsis = SYNOPSIS
+man-section-description = DESCRIPTION
+man-section-version = VERSION
+man-section-authors = AUTHORS
+doctor-about = Check the environment the runs would see: the language files, cargo and rustup, the cargo variables, the root and the manifests. The output is meant to be pasted into a report.
+doctor-program = rust_recursively_action { $version }
+doctor-language-dir = Language files: `{ $path }`, { $origin }.
+doctor-language = Language: `{ $lang }` for the requested `{ $desired }`, with the match score { $score } of 15.
+doctor-language-builtin = Language: the built-in `{ $lang }` messages, no language files are found, see `--print-lang-search-path`.
+doctor-language-pseudo = Language: the pseudo-locale `{ $lang }`.
+doctor-version = { $program }: { $version }
+doctor-command-failed = `{ $command }` can't be run: { $reason }
+doctor-variable-set = { $name }: `{ $value }`
+doctor-variable-not-set = { $name }: not set
+doctor-root-writable = Root `{ $path }`: writable.
+doctor-root-not-writable = Root `{ $path }`: not writable, { $reason }
+doctor-manifest-broken = Invalid manifest `{ $path }`: { $problem }
+doctor-summary = { $problems ->
+    [0] No problem found.
+    [one] Found 1 problem.
+   *[other] Found { $problems } problems.
+}
+manifest-skipped = Skipped `{ $path }`: { $problem } See the `validate` command.
+manifest-problem-unreadable = can't be read, { $reason }.
+manifest-problem-empty = the manifest is empty.
+manifest-problem-placeholder = the template placeholder `{ $placeholder }` is left in it.
+manifest-problem-syntax = not valid TOML, { $reason }.
+manifest-problem-no-package = neither a `[package]` nor a `[workspace]` table is found.
+validate-about = Check the manifests of the discovered projects, and print where each invalid one can't be parsed. The invalid ones are always left out of the runs.
+validate-summary = { $invalid ->
+    [0] { $projects ->
+        [0] No project is discovered.
+        [one] The manifest of the discovered project is valid.
+       *[other] The manifests of all { $projects } discovered projects are valid.
+    }
+   *[other] { $invalid ->
+        [one] { $projects ->
+            [one] The manifest of the discovered project is invalid.
+           *[other] { $invalid } of the { $projects } discovered manifests is invalid.
+        }
+       *[other] { $invalid } of the { $projects } discovered manifests are invalid.
+    }
+}
+clean-release-helper = Clean only the `release` profile, passed to `cargo clean`.
+clean-profile-helper = Clean only the named profile, passed to `cargo clean`.
+clean-target-helper = Clean only the output of the target triple, passed to `cargo clean`, can be repeated.
+clean-doc-helper = Clean only the documentation in `target/doc`, passed to `cargo clean`.
+clean-only-helper = Instead of `cargo clean`, remove only these files from the `target` directory of every project. `--release`, `--profile` and `--target` narrow it down.
+clean-only-incremental-helper = The `incremental` directories, the builds themselves are kept.
+clean-only-stale-deps-helper = The artifacts in `deps` built by another rustc than the latest build of each profile, like the ones of an old toolchain.
+clean-only-nothing = Nothing to remove in `{ $project }`.
+clean-options-need-clean = The clean options only apply to `--gs clean`, they're ignored.
+error-clean-only-in-build-file = `--clean-only` can't be written to a Makefile or a build.ninja.
+hint-clean-only-in-build-file = Run it with `--gt run-as-subprocess`, or print the commands with `--gt bash-commands`.
+create-directory-failed = Create the directory `{ $dir_path }` failed.
+remove-file-failed = Remove the file `{ $file_path }` failed.
+error-no-data-dir = Can't locate the home directory to install into.
+hint-no-data-dir = Pass the installation prefix with `--prefix`, or set `XDG_DATA_HOME`.
+hint-remove-file = Check the permissions of the directory holding the file.
@@ -3,10 +3,242 @@ root-path = 根目录: { $root_path }。
 read-directory-failed = 读取文件夹{ $dir_path }失败。
 read-directory-entry-failed = 读取文件夹条目失败。
 get-file-name-failed = 读取文件名称失败。
-osstring-to-string-failed = OsString转换为String失败。
 get-metadata-error = 获取文件元数据失败, 于{ $dir_path }。
 generate-bash-like-cmds-helper = 生成bash-like指令。
 directly-run-helper = 作为子进程直接执行。
 dry-run-helper = 仅输出动作序列。
-pathbuf-to-str-failed = PathBuf转为String失败。
 start-cargo-subcommand-failed = 启动Cargo { $subcommand } 失败。
+read-file-failed = 读取文件{ $file_path }失败。
+parse-toml-failed = 解析TOML文件{ $file_path }失败。
+inventory-about = 列出所有项目的依赖, 及其使用中的版本和使用它们的项目。
+inventory-format-helper = 依赖清单的输出格式。
+inventory-multi-major-only-helper = 仅列出以多个主版本使用的crate。
+inventory-multi-major = Crate `{ $crate_name }`被以多个主版本使用: { $series }。
+audit-about = 以本地的RustSec advisory-db检查所有项目的`Cargo.lock`。
+audit-db-helper = advisory-db的路径, 默认为`~/.cargo/advisory-db`。
+audit-no-yanked-helper = 不以本地registry索引缓存检查被撤回(yanked)的版本。
+audit-deny-warnings-helper = 存在提示性公告或被撤回的crate时也以失败退出。
+audit-no-default-db = 无法定位主目录, 请以`--db`指定advisory-db的路径。
+audit-skip-advisory = 跳过无法解析的公告{ $file_path }。
+audit-vulnerability = { $advisory_id } { $crate_name } { $version }: { $title } (已修复于: { $patched })
+audit-informational = { $advisory_id } [{ $kind }] { $crate_name } { $version }: { $title }
+audit-yanked = 已撤回: { $crate_name } { $version }
+audit-summary = 在{ $projects }个项目中发现{ $vulnerabilities }个漏洞和{ $warnings }个警告。
+interactive-helper = 运行前从发现的项目中挑选要处理的项目。
+selection-item = { $project }  ({ BYTES($bytes) }, 修改于{ $age })
+selection-checklist-prompt = 选择要处理的项目(空格键切换, 回车键确认)
+selection-numbered-prompt = 以编号选择项目(如`1 3 5-7`, `all`), 留空则不选择:
+selection-invalid-answer = 无法理解`{ $answer }`, 请使用上方列出的编号。
+age-unknown = 未知时间
+age-minutes = { $minutes }分钟前
+age-hours = { $hours }小时前
+age-days = { $days }天前
+jobs-helper = 作为子进程执行时同时处理的项目数上限, 项目会在其路径依赖完成后才开始。
+git-dirty-only-helper = 仅处理git仓库中有修改、暂存或未追踪文件的项目。
+git-clean-only-helper = 仅处理git仓库中没有任何变更的项目。
+skip-untracked-repos-helper = 跳过不在git仓库中或清单文件未被追踪的项目。
+changed-since-helper = 仅处理自所在仓库的指定提交以来有文件变更的项目。
+start-git-failed = 于{ $dir_path }启动`git`失败。
+git-commit-helper = `update`成功后, 在运行前没有未提交变更的git仓库中提交变更的`Cargo.lock`。
+git-commit-skip-dirty = 不在{ $repo }中提交, 它在运行前有未提交的变更。
+git-commit-nothing = { $project }没有需要提交的内容, `Cargo.lock`不会变更。
+git-commit-new-lock = 将在{ $project }创建并提交新的`Cargo.lock`。
+git-commit-done = 已在{ $repo }中提交{ $project }更新的`Cargo.lock`。
+toolchain-helper = 在每个项目中执行`cargo +TOOLCHAIN`, 重复指定以对每个列出的工具链各执行一次。
+toolchain-overridden = 工具链{ $toolchain }, 覆盖{ $file_path }中的{ $file_toolchain }
+toolchain-explicit = 工具链{ $toolchain }
+toolchain-from-file = 来自{ $file_path }的工具链{ $toolchain }
+toolchain-default = 默认工具链
+run-toolchain = { $project }: { $toolchain }
+env-helper = 为cargo设置环境变量, 如`CARGO_TARGET_DIR=/tmp/t`, 可重复指定。
+env-file-helper = 从`KEY=VALUE`格式的文件中读取cargo的环境变量。
+cargo-bin-helper = 执行此程序而非`cargo`, 如`cross`或包装脚本。
+env-pair-invalid = `{ $pair }`不是`KEY=VALUE`的格式。
+env-name-invalid = `{ $name }`不是变量名, 变量名由字母、数字和`_`组成, 且不以数字开头。
+makefile-helper = 生成每个项目一个phony目标并带有`all`目标的Makefile。
+ninja-helper = 生成每个项目一个目标并带有`all`目标的`build.ninja`。
+list-helper = 仅输出发现的项目目录, 每行一个, 不带任何标题。
+print0-helper = 以NUL而非换行分隔列出的路径, 用于`xargs -0`。
+manifest-paths-helper = 列出`Cargo.toml`文件的路径而非目录。
+from-stdin-helper = 从标准输入读取项目目录(每行一个或以NUL分隔), 而不遍历根目录。
+not-a-cargo-project = 跳过`{ $path }`, 它不是带有`Cargo.toml`的目录。
+report-helper = 输出运行报告: 供CI服务器使用的JUnit XML、Markdown表格或HTML页面。需要`--gt run-as-subprocess`。
+report-output-helper = 将报告写入文件而非标准输出。
+report-needs-subprocess = 未生成报告, 只有以子进程运行时才有结果可报告。
+write-file-failed = 写入文件`{ $file_path }`失败。
+report-title = Cargo 运行结果
+report-summary = 共运行{ $total }次, { $failures }次失败。
+report-project = 项目
+report-action = 操作
+report-toolchain = 工具链
+report-status = 状态
+report-duration = 耗时
+report-changes = 变更
+report-passed = 通过
+report-failed = 失败({ $reason })
+report-reclaimed = 释放了{ BYTES($bytes) }
+run-summary = 共运行{ $total }次, { $failures ->
+        [0] 全部成功
+       *[other] { $failures }次失败
+    }。
+run-reclaimed = 共释放了{ BYTES($bytes) }。
+report-failures = 失败详情
+verbose-helper = 显示错误发生的位置及其回溯。
+lang-helper = 消息使用的语言, 例如`zh_TW`, 代替系统的语言。`en-XA`是由带重音的英文组成的伪语言, 用于找出未翻译的文本。
+error-caused-by = 原因
+error-hint = 提示
+error-location = 位置
+error-backtrace = 回溯
+error-current-dir = 读取当前目录失败。
+error-process-exit = cargo进程以代码{ $code }退出。
+error-process-killed = cargo进程被信号终止。
+error-language-files-not-found = 在`{ $path }`找不到语言文件。
+error-language-negotiated = 没有与`{ $lang }`匹配的已安装语言。
+error-advisory-db-not-found = 在`{ $path }`找不到安全公告数据库。
+error-std-io = 读取标准输入或写入标准输出失败。
+error-selection-prompt = 选择提示失败。
+error-dependency-cycle = 路径依赖形成了循环: { $cycle }。
+error-git-command = 在`{ $dir_path }`中执行`git { $args }`失败。
+error-env-file-line = `{ $file_path }`的第{ $line_number }行不是`KEY=VALUE`对: { $line }
+error-serialize-json = 序列化JSON输出失败。
+hint-canonicalizing = 请检查路径是否存在且可读。
+hint-report-bug = 这是一个bug, 请附上`--verbose`的输出报告它。
+hint-read-dir = 请检查目录的权限, 或它是否在运行期间被删除。
+hint-path-file-name = 请传入不以`..`结尾的路径。
+hint-current-dir = 当前目录可能已被删除, 请切换到存在的目录。
+hint-process-exit = 请阅读上方cargo的输出。
+hint-language-files = 将`i18n`目录放入`$XDG_DATA_HOME/rust_recursive_action`(默认为`~/.local/share/rust_recursive_action`), 或将`RUST_RECURSIVELY_ACTION_PATH`设为包含它的目录。`--print-lang-search-path`会列出查找过的位置。
+hint-language-negotiated = 将`LANG`设为已安装的语言之一, 例如`en-US`。
+hint-read-file = 请检查文件是否存在且可读。
+hint-write-file = 请检查文件所在目录是否存在且可写。
+hint-parse-toml = 请修正上方位置的TOML语法。
+hint-advisory-db = 使用`git clone https://github.com/rustsec/advisory-db ~/.cargo/advisory-db`克隆它, 或用`--db`传入其路径。
+hint-std-io = 管道的另一端可能已关闭。
+hint-selection-prompt = 请在终端中运行, 或使用`--from-stdin`传入项目。
+hint-dependency-cycle = 请移除循环中的某个路径依赖。
+hint-start-git = 请安装git, 或确认它在`PATH`中。
+hint-git-command = 在该目录中运行此git命令以查看完整错误。
+hint-env-file-line = 请将该行写成`KEY=VALUE`, 名称由字母、数字和`_`组成, 或用`#`将其注释掉。
+hint-start-cargo = 请安装cargo, 或用`--cargo-bin`传入要运行的程序。
+hint-metadata = 请修复或删除该条目, 损坏的符号链接或缺少权限会导致此问题。
+error-language-io = 读取`{ $path }`的语言文件失败, 将使用内置的英文消息。
+error-parse-fluent = 语言文件`{ $file_path }`有错误, 将使用内置的英文消息: { $errors }
+error-parse-language-id = `{ $lang }`不是像`en-US`这样的语言标识符。
+hint-parse-fluent = 请修正该文件的语法, 或重新安装`i18n`目录。
+about = 递归地清理/更新cargo项目的工具, 从根目录到叶目录, 并跳过目录: `src`, `.*`, `target`
+target-dir-helper = 开始搜索的目录, 默认为当前目录。
+generating-type-helper = 如何处理发现的项目: 打印命令, 运行命令, 或者写出构建文件。
+generating-subcommand-helper = 在每个项目中运行的cargo子命令。
+subcommand-clean-helper = `cargo clean`, 删除构建产物。
+subcommand-update-helper = `cargo update`, 更新`Cargo.lock`中的依赖。
+subcommand-build-helper = `cargo build`, 编译项目。
+subcommand-check-helper = `cargo check`, 检查项目的错误而不构建。
+subcommand-test-helper = `cargo test`, 运行测试。
+subcommand-clippy-helper = `cargo clippy`, 运行lint检查。
+report-junit-helper = JUnit XML, 大多数CI服务器都能读取。
+report-markdown-helper = Markdown表格, 可以粘贴到issue中。
+report-html-helper = 独立的HTML页面。
+inventory-csv-helper = 逗号分隔值, 每个crate版本和项目一行。
+inventory-json-helper = JSON文档。
+help-helper = 打印帮助
+version-helper = 打印版本
+help-subcommand-about = 打印此消息或给定子命令的帮助
+help-usage-heading = 用法:
+help-arguments-heading = 参数
+help-options-heading = 选项
+help-commands-heading = 命令
+help-command-value-name = 命令
+help-possible-values-heading = 可能的值
+help-default-value = 默认值: { $value }
+clap-unknown-argument = 意外的参数'{ $arg }'
+clap-invalid-value = '{ $arg }'的值'{ $value }'无效
+clap-value-required = '{ $arg }'需要一个值, 但没有提供
+clap-value-validation = '{ $arg }'的值'{ $value }'无效: { $reason }
+clap-invalid-subcommand = 无法识别的子命令'{ $subcommand }'
+clap-argument-conflict = 参数'{ $arg }'不能与'{ $other }'一起使用
+clap-missing-required = 没有提供以下必需的参数: { $args }
+clap-similar-argument = 存在相似的参数: '{ $arg }'
+clap-similar-value = 存在相似的值: '{ $value }'
+clap-similar-subcommand = 存在相似的子命令: '{ $subcommand }'
+clap-pass-as-value = 要把'{ $arg }'作为值传递, 请使用'-- { $arg }'
+clap-possible-values = 可能的值: { $values }
+clap-more-information = 更多信息, 请尝试'--help'。
+print-lang-search-path-helper = 打印查找语言文件的每个位置, 以及每个位置未被使用的原因。
+lang-search-entry = { $index }. { $path } ({ $origin }): { $status }
+lang-search-origin-cwd = 当前目录
+lang-search-origin-env = `{ $var }`
+lang-search-origin-default = 未设置的`{ $var }`的默认值
+lang-search-origin-default-or-relative = `{ $var }`未设置或为相对路径时的默认值
+lang-search-origin-exe = 可执行文件所在目录旁的`share`目录
+lang-search-used = 已使用
+lang-search-shadowed = 已找到, 但使用了更前面的位置
+lang-search-not-set = 未设置
+lang-search-relative = 已忽略, 不允许相对路径
+lang-search-missing = 不存在
+lang-search-not-a-directory = 不是目录
+lang-search-builtin = 都没有找到, 使用内置的英文消息。
+install-data-about = 将语言文件安装或更新到数据目录, 按要求同时安装shell补全和man手册。
+install-data-prefix-helper = 安装到`PREFIX/share`下, 例如`/usr/local`, 而不是`$XDG_DATA_HOME`(`~/.local/share`)。
+install-data-uninstall-helper = 改为删除已安装的文件, 包括补全和man手册。
+install-data-completions-helper = 同时安装bash、zsh和fish的补全。
+install-data-man-helper = 同时安装man手册。
+install-data-installed = 已安装`{ $path }`。
+install-data-unchanged = `{ $path }`已是最新。
+install-data-removed = 已删除`{ $path }`。
+install-data-nothing-removed = `{ $path }`下没有安装任何文件。
+install-data-not-searched = 本程序不会在`{ $path }`查找语言文件, 参见`--print-lang-search-path`。
+completions-about = 输出shell的补全脚本, 使用帮助的语言。
+completions-shell-helper = 脚本所针对的shell。
+man-about = 输出roff格式的man手册, 使用帮助的语言。
+man-section-name = 名称
+man-section-synopsis = 概要
+man-section-description = 描述
+man-section-version = 版本
+man-section-authors = 作者
+doctor-about = 检查运行时所处的环境: 语言文件、cargo和rustup、cargo的环境变量、根目录和各清单文件。输出可直接粘贴到问题报告中。
+doctor-program = rust_recursively_action { $version }
+doctor-language-dir = 语言文件: `{ $path }`, { $origin }。
+doctor-language = 语言: 为请求的`{ $desired }`选用了`{ $lang }`, 匹配分数为{ $score }/15。
+doctor-language-builtin = 语言: 内置的`{ $lang }`消息, 未找到语言文件, 参见`--print-lang-search-path`。
+doctor-language-pseudo = 语言: 伪区域设置`{ $lang }`。
+doctor-version = { $program }: { $version }
+doctor-command-failed = 无法运行`{ $command }`: { $reason }
+doctor-variable-set = { $name }: `{ $value }`
+doctor-variable-not-set = { $name }: 未设置
+doctor-root-writable = 根目录`{ $path }`: 可写。
+doctor-root-not-writable = 根目录`{ $path }`: 不可写, { $reason }
+doctor-manifest-broken = 无效的清单文件`{ $path }`: { $problem }
+doctor-summary = { $problems ->
+    [0] 未发现问题。
+   *[other] 发现{ $problems }个问题。
+}
+manifest-skipped = 已跳过`{ $path }`: { $problem } 参见`validate`命令。
+manifest-problem-unreadable = 无法读取, { $reason }。
+manifest-problem-empty = 清单文件为空。
+manifest-problem-placeholder = 其中留有模板占位符`{ $placeholder }`。
+manifest-problem-syntax = 不是有效的TOML, { $reason }。
+manifest-problem-no-package = 既没有`[package]`表也没有`[workspace]`表。
+validate-about = 检查发现的项目的清单文件, 并输出每个无效清单文件无法解析的位置。无效的清单文件总是不参与运行。
+validate-summary = { $invalid ->
+    [0] { $projects ->
+        [0] 没有发现项目。
+       *[other] 发现的{ $projects }个项目的清单文件都有效。
+    }
+   *[other] 发现的{ $projects }个清单文件中有{ $invalid }个无效。
+}
+clean-release-helper = 只清理`release`配置, 传给`cargo clean`。
+clean-profile-helper = 只清理指定的配置, 传给`cargo clean`。
+clean-target-helper = 只清理该目标三元组的输出, 传给`cargo clean`, 可重复指定。
+clean-doc-helper = 只清理`target/doc`中的文档, 传给`cargo clean`。
+clean-only-helper = 不运行`cargo clean`, 只从每个项目的`target`目录删除这些文件。可用`--release`、`--profile`和`--target`缩小范围。
+clean-only-incremental-helper = `incremental`目录, 保留构建结果本身。
+clean-only-stale-deps-helper = `deps`中由不同于各配置最近一次构建的rustc生成的产物, 例如旧工具链的产物。
+clean-only-nothing = `{ $project }`中没有要删除的文件。
+clean-options-need-clean = 清理选项只适用于`--gs clean`, 已忽略。
+error-clean-only-in-build-file = `--clean-only`无法写入Makefile或build.ninja。
+hint-clean-only-in-build-file = 请使用`--gt run-as-subprocess`直接运行, 或用`--gt bash-commands`输出命令。
+create-directory-failed = 创建目录`{ $dir_path }`失败。
+remove-file-failed = 删除文件`{ $file_path }`失败。
+error-no-data-dir = 无法定位要安装到的主目录。
+hint-no-data-dir = 用`--prefix`指定安装前缀, 或设置`XDG_DATA_HOME`。
+hint-remove-file = 请检查文件所在目录的权限。
@@ -3,10 +3,242 @@ root-path = 根目錄: { $root_path }。
 read-directory-failed = 讀取檔案夾{ $dir_path }失敗。
 read-directory-entry-failed = 讀取檔案夾項目失敗。
 get-file-name-failed = 讀取檔名稱失敗。
-osstring-to-string-failed = OsString轉換為String失敗。
 get-metadata-error = 抓取檔案元資料失敗於{ $dir_path }。
 generate-bash-like-cmds-helper = 生成bash-like指令。
 directly-run-helper = 作為子程式直接執行。
 dry-run-helper = 僅匯出動作串列。
-pathbuf-to-str-failed = PathBuf轉為String失敗。
 start-cargo-subcommand-failed = 啟動Cargo { $subcommand } 失敗。
+read-file-failed = 讀取檔案{ $file_path }失敗。
+parse-toml-failed = 解析TOML檔案{ $file_path }失敗。
+inventory-about = 列出所有專案的相依套件, 及其使用中的版本和使用它們的專案。
+inventory-format-helper = 相依清單的匯出格式。
+inventory-multi-major-only-helper = 僅列出以多個主版本使用的crate。
+inventory-multi-major = Crate `{ $crate_name }`被以多個主版本使用: { $series }。
+audit-about = 以本機的RustSec advisory-db檢查所有專案的`Cargo.lock`。
+audit-db-helper = advisory-db的路徑, 預設為`~/.cargo/advisory-db`。
+audit-no-yanked-helper = 不以本機registry索引快取檢查被撤回(yanked)的版本。
+audit-deny-warnings-helper = 存在提示性公告或被撤回的crate時也以失敗結束。
+audit-no-default-db = 無法定位家目錄, 請以`--db`指定advisory-db的路徑。
+audit-skip-advisory = 略過無法解析的公告{ $file_path }。
+audit-vulnerability = { $advisory_id } { $crate_name } { $version }: { $title } (已修復於: { $patched })
+audit-informational = { $advisory_id } [{ $kind }] { $crate_name } { $version }: { $title }
+audit-yanked = 已撤回: { $crate_name } { $version }
+audit-summary = 在{ $projects }個專案中發現{ $vulnerabilities }個漏洞和{ $warnings }個警告。
+interactive-helper = 執行前從找到的專案中挑選要處理的專案。
+selection-item = { $project }  ({ BYTES($bytes) }, 修改於{ $age })
+selection-checklist-prompt = 選擇要處理的專案(空白鍵切換, Enter鍵確認)
+selection-numbered-prompt = 以編號選擇專案(如`1 3 5-7`, `all`), 留空則不選擇:
+selection-invalid-answer = 無法理解`{ $answer }`, 請使用上方列出的編號。
+age-unknown = 未知時間
+age-minutes = { $minutes }分鐘前
+age-hours = { $hours }小時前
+age-days = { $days }天前
+jobs-helper = 作為子程式執行時同時處理的專案數上限, 專案會在其路徑相依完成後才開始。
+git-dirty-only-helper = 僅處理git儲存庫中有修改、暫存或未追蹤檔案的專案。
+git-clean-only-helper = 僅處理git儲存庫中沒有任何變更的專案。
+skip-untracked-repos-helper = 略過不在git儲存庫中或清單檔案未被追蹤的專案。
+changed-since-helper = 僅處理自所在儲存庫的指定提交以來有檔案變更的專案。
+start-git-failed = 於{ $dir_path }啟動`git`失敗。
+git-commit-helper = `update`成功後, 在執行前沒有未提交變更的git儲存庫中提交變更的`Cargo.lock`。
+git-commit-skip-dirty = 不在{ $repo }中提交, 它在執行前有未提交的變更。
+git-commit-nothing = { $project }沒有需要提交的內容, `Cargo.lock`不會變更。
+git-commit-new-lock = 將在{ $project }建立並提交新的`Cargo.lock`。
+git-commit-done = 已在{ $repo }中提交{ $project }更新的`Cargo.lock`。
+toolchain-helper = 在每個專案中執行`cargo +TOOLCHAIN`, 重複指定以對每個列出的工具鏈各執行一次。
+toolchain-overridden = 工具鏈{ $toolchain }, 覆寫{ $file_path }中的{ $file_toolchain }
+toolchain-explicit = 工具鏈{ $toolchain }
+toolchain-from-file = 來自{ $file_path }的工具鏈{ $toolchain }
+toolchain-default = 預設工具鏈
+run-toolchain = { $project }: { $toolchain }
+env-helper = 為cargo設定環境變數, 如`CARGO_TARGET_DIR=/tmp/t`, 可重複指定。
+env-file-helper = 從`KEY=VALUE`格式的檔案中讀取cargo的環境變數。
+cargo-bin-helper = 執行此程式而非`cargo`, 如`cross`或包裝腳本。
+env-pair-invalid = `{ $pair }`不是`KEY=VALUE`的格式。
+env-name-invalid = `{ $name }`不是變數名稱, 變數名稱由字母、數字和`_`組成, 且不以數字開頭。
+makefile-helper = 產生每個專案一個phony目標並帶有`all`目標的Makefile。
+ninja-helper = 產生每個專案一個目標並帶有`all`目標的`build.ninja`。
+list-helper = 僅輸出找到的專案目錄, 每行一個, 不帶任何標題。
+print0-helper = 以NUL而非換行分隔列出的路徑, 用於`xargs -0`。
+manifest-paths-helper = 列出`Cargo.toml`檔案的路徑而非目錄。
+from-stdin-helper = 從標準輸入讀取專案目錄(每行一個或以NUL分隔), 而不走訪根目錄。
+not-a-cargo-project = 略過`{ $path }`, 它不是帶有`Cargo.toml`的目錄。
+report-helper = 輸出執行報告: 供CI伺服器使用的JUnit XML、Markdown表格或HTML頁面。需要`--gt run-as-subprocess`。
+report-output-helper = 將報告寫入檔案而非標準輸出。
+report-needs-subprocess = 未產生報告, 只有以子行程執行時才有結果可報告。
+write-file-failed = 寫入檔案`{ $file_path }`失敗。
+report-title = Cargo 執行結果
+report-summary = 共執行{ $total }次, { $failures }次失敗。
+report-project = 專案
+report-action = 操作
+report-toolchain = 工具鏈
+report-status = 狀態
+report-duration = 耗時
+report-changes = 變更
+report-passed = 通過
+report-failed = 失敗({ $reason })
+report-reclaimed = 釋放了{ BYTES($bytes) }
+run-summary = 共執行{ $total }次, { $failures ->
+        [0] 全部成功
+       *[other] { $failures }次失敗
+    }。
+run-reclaimed = 共釋放了{ BYTES($bytes) }。
+report-failures = 失敗詳情
+verbose-helper = 顯示錯誤發生的位置及其回溯。
+lang-helper = 訊息使用的語言, 例如`zh_TW`, 取代系統的語言。`en-XA`是由帶重音的英文組成的偽語言, 用於找出未翻譯的文字。
+error-caused-by = 原因
+error-hint = 提示
+error-location = 位置
+error-backtrace = 回溯
+error-current-dir = 讀取目前目錄失敗。
+error-process-exit = cargo行程以代碼{ $code }結束。
+error-process-killed = cargo行程被訊號終止。
+error-language-files-not-found = 在`{ $path }`找不到語言檔案。
+error-language-negotiated = 沒有與`{ $lang }`相符的已安裝語言。
+error-advisory-db-not-found = 在`{ $path }`找不到安全公告資料庫。
+error-std-io = 讀取標準輸入或寫入標準輸出失敗。
+error-selection-prompt = 選擇提示失敗。
+error-dependency-cycle = 路徑相依形成了循環: { $cycle }。
+error-git-command = 在`{ $dir_path }`中執行`git { $args }`失敗。
+error-env-file-line = `{ $file_path }`的第{ $line_number }行不是`KEY=VALUE`對: { $line }
+error-serialize-json = 序列化JSON輸出失敗。
+hint-canonicalizing = 請檢查路徑是否存在且可讀。
+hint-report-bug = 這是一個bug, 請附上`--verbose`的輸出回報它。
+hint-read-dir = 請檢查目錄的權限, 或它是否在執行期間被刪除。
+hint-path-file-name = 請傳入不以`..`結尾的路徑。
+hint-current-dir = 目前目錄可能已被刪除, 請切換到存在的目錄。
+hint-process-exit = 請閱讀上方cargo的輸出。
+hint-language-files = 將`i18n`目錄放入`$XDG_DATA_HOME/rust_recursive_action`(預設為`~/.local/share/rust_recursive_action`), 或將`RUST_RECURSIVELY_ACTION_PATH`設為包含它的目錄。`--print-lang-search-path`會列出尋找過的位置。
+hint-language-negotiated = 將`LANG`設為已安裝的語言之一, 例如`en-US`。
+hint-read-file = 請檢查檔案是否存在且可讀。
+hint-write-file = 請檢查檔案所在目錄是否存在且可寫入。
+hint-parse-toml = 請修正上方位置的TOML語法。
+hint-advisory-db = 使用`git clone https://github.com/rustsec/advisory-db ~/.cargo/advisory-db`複製它, 或用`--db`傳入其路徑。
+hint-std-io = 管線的另一端可能已關閉。
+hint-selection-prompt = 請在終端機中執行, 或使用`--from-stdin`傳入專案。
+hint-dependency-cycle = 請移除循環中的某個路徑相依。
+hint-start-git = 請安裝git, 或確認它在`PATH`中。
+hint-git-command = 在該目錄中執行此git命令以檢視完整錯誤。
+hint-env-file-line = 請將該行寫成`KEY=VALUE`, 名稱由字母、數字和`_`組成, 或用`#`將其註解掉。
+hint-start-cargo = 請安裝cargo, 或用`--cargo-bin`傳入要執行的程式。
+hint-metadata = 請修復或刪除該項目, 損壞的符號連結或缺少權限會導致此問題。
+error-language-io = 讀取`{ $path }`的語言檔案失敗, 將使用內建的英文訊息。
+error-parse-fluent = 語言檔案`{ $file_path }`有錯誤, 將使用內建的英文訊息: { $errors }
+error-parse-language-id = `{ $lang }`不是像`en-US`這樣的語言識別碼。
+hint-parse-fluent = 請修正該檔案的語法, 或重新安裝`i18n`目錄。
+about = 遞迴地清理/更新cargo專案的工具, 從根目錄到葉目錄, 並跳過目錄: `src`, `.*`, `target`
+target-dir-helper = 開始搜尋的目錄, 預設為目前目錄。
+generating-type-helper = 如何處理找到的專案: 印出命令, 執行命令, 或者寫出建置檔案。
+generating-subcommand-helper = 在每個專案中執行的cargo子命令。
+subcommand-clean-helper = `cargo clean`, 刪除建置產物。
+subcommand-update-helper = `cargo update`, 更新`Cargo.lock`中的相依套件。
+subcommand-build-helper = `cargo build`, 編譯專案。
+subcommand-check-helper = `cargo check`, 檢查專案的錯誤而不建置。
+subcommand-test-helper = `cargo test`, 執行測試。
+subcommand-clippy-helper = `cargo clippy`, 執行lint檢查。
+report-junit-helper = JUnit XML, 大多數CI伺服器都能讀取。
+report-markdown-helper = Markdown表格, 可以貼到issue中。
+report-html-helper = 獨立的HTML頁面。
+inventory-csv-helper = 逗號分隔值, 每個crate版本和專案一列。
+inventory-json-helper = JSON文件。
+help-helper = 印出說明
+version-helper = 印出版本
+help-subcommand-about = 印出此訊息或給定子命令的說明
+help-usage-heading = 用法:
+help-arguments-heading = 參數
+help-options-heading = 選項
+help-commands-heading = 命令
+help-command-value-name = 命令
+help-possible-values-heading = 可能的值
+help-default-value = 預設值: { $value }
+clap-unknown-argument = 非預期的參數'{ $arg }'
+clap-invalid-value = '{ $arg }'的值'{ $value }'無效
+clap-value-required = '{ $arg }'需要一個值, 但沒有提供
+clap-value-validation = '{ $arg }'的值'{ $value }'無效: { $reason }
+clap-invalid-subcommand = 無法辨識的子命令'{ $subcommand }'
+clap-argument-conflict = 參數'{ $arg }'不能與'{ $other }'一起使用
+clap-missing-required = 沒有提供以下必要的參數: { $args }
+clap-similar-argument = 存在相似的參數: '{ $arg }'
+clap-similar-value = 存在相似的值: '{ $value }'
+clap-similar-subcommand = 存在相似的子命令: '{ $subcommand }'
+clap-pass-as-value = 要把'{ $arg }'當作值傳遞, 請使用'-- { $arg }'
+clap-possible-values = 可能的值: { $values }
+clap-more-information = 更多資訊, 請嘗試'--help'。
+print-lang-search-path-helper = 印出尋找語言檔案的每個位置, 以及每個位置未被使用的原因。
+lang-search-entry = { $index }. { $path } ({ $origin }): { $status }
+lang-search-origin-cwd = 目前目錄
+lang-search-origin-env = `{ $var }`
+lang-search-origin-default = 未設定的`{ $var }`的預設值
+lang-search-origin-default-or-relative = `{ $var }`未設定或為相對路徑時的預設值
+lang-search-origin-exe = 執行檔所在目錄旁的`share`目錄
+lang-search-used = 已使用
+lang-search-shadowed = 已找到, 但使用了更前面的位置
+lang-search-not-set = 未設定
+lang-search-relative = 已忽略, 不允許相對路徑
+lang-search-missing = 不存在
+lang-search-not-a-directory = 不是目錄
+lang-search-builtin = 都沒有找到, 使用內建的英文訊息。
+install-data-about = 將語言檔案安裝或更新到資料目錄, 依要求同時安裝shell補全和man手冊。
+install-data-prefix-helper = 安裝到`PREFIX/share`下, 例如`/usr/local`, 而不是`$XDG_DATA_HOME`(`~/.local/share`)。
+install-data-uninstall-helper = 改為刪除已安裝的檔案, 包括補全和man手冊。
+install-data-completions-helper = 同時安裝bash、zsh和fish的補全。
+install-data-man-helper = 同時安裝man手冊。
+install-data-installed = 已安裝`{ $path }`。
+install-data-unchanged = `{ $path }`已是最新。
+install-data-removed = 已刪除`{ $path }`。
+install-data-nothing-removed = `{ $path }`下沒有安裝任何檔案。
+install-data-not-searched = 本程式不會在`{ $path }`尋找語言檔案, 參見`--print-lang-search-path`。
+completions-about = 輸出shell的補全腳本, 使用說明的語言。
+completions-shell-helper = 腳本所針對的shell。
+man-about = 輸出roff格式的man手冊, 使用說明的語言。
+man-section-name = 名稱
+man-section-synopsis = 概要
+man-section-description = 描述
+man-section-version = 版本
+man-section-authors = 作者
+doctor-about = 檢查執行時所處的環境: 語言檔案、cargo和rustup、cargo的環境變數、根目錄和各清單檔案。輸出可直接貼到問題回報中。
+doctor-program = rust_recursively_action { $version }
+doctor-language-dir = 語言檔案: `{ $path }`, { $origin }。
+doctor-language = 語言: 為請求的`{ $desired }`選用了`{ $lang }`, 匹配分數為{ $score }/15。
+doctor-language-builtin = 語言: 內建的`{ $lang }`訊息, 找不到語言檔案, 參見`--print-lang-search-path`。
+doctor-language-pseudo = 語言: 偽地區設定`{ $lang }`。
+doctor-version = { $program }: { $version }
+doctor-command-failed = 無法執行`{ $command }`: { $reason }
+doctor-variable-set = { $name }: `{ $value }`
+doctor-variable-not-set = { $name }: 未設定
+doctor-root-writable = 根目錄`{ $path }`: 可寫入。
+doctor-root-not-writable = 根目錄`{ $path }`: 無法寫入, { $reason }
+doctor-manifest-broken = 無效的清單檔案`{ $path }`: { $problem }
+doctor-summary = { $problems ->
+    [0] 未發現問題。
+   *[other] 發現{ $problems }個問題。
+}
+manifest-skipped = 已略過`{ $path }`: { $problem } 參見`validate`命令。
+manifest-problem-unreadable = 無法讀取, { $reason }。
+manifest-problem-empty = 清單檔案為空。
+manifest-problem-placeholder = 其中留有範本預留位置`{ $placeholder }`。
+manifest-problem-syntax = 不是有效的TOML, { $reason }。
+manifest-problem-no-package = 既沒有`[package]`表也沒有`[workspace]`表。
+validate-about = 檢查發現的專案的清單檔案, 並輸出每個無效清單檔案無法解析的位置。無效的清單檔案一律不參與執行。
+validate-summary = { $invalid ->
+    [0] { $projects ->
+        [0] 沒有發現專案。
+       *[other] 發現的{ $projects }個專案的清單檔案都有效。
+    }
+   *[other] 發現的{ $projects }個清單檔案中有{ $invalid }個無效。
+}
+clean-release-helper = 只清理`release`設定檔, 傳給`cargo clean`。
+clean-profile-helper = 只清理指定的設定檔, 傳給`cargo clean`。
+clean-target-helper = 只清理該目標三元組的輸出, 傳給`cargo clean`, 可重複指定。
+clean-doc-helper = 只清理`target/doc`中的文件, 傳給`cargo clean`。
+clean-only-helper = 不執行`cargo clean`, 只從每個專案的`target`目錄刪除這些檔案。可用`--release`、`--profile`和`--target`縮小範圍。
+clean-only-incremental-helper = `incremental`目錄, 保留建置結果本身。
+clean-only-stale-deps-helper = `deps`中由不同於各設定檔最近一次建置的rustc產生的產物, 例如舊工具鏈的產物。
+clean-only-nothing = `{ $project }`中沒有要刪除的檔案。
+clean-options-need-clean = 清理選項只適用於`--gs clean`, 已忽略。
+error-clean-only-in-build-file = `--clean-only`無法寫入Makefile或build.ninja。
+hint-clean-only-in-build-file = 請使用`--gt run-as-subprocess`直接執行, 或用`--gt bash-commands`輸出命令。
+create-directory-failed = 建立目錄`{ $dir_path }`失敗。
+remove-file-failed = 刪除檔案`{ $file_path }`失敗。
+error-no-data-dir = 無法找到要安裝到的主目錄。
+hint-no-data-dir = 用`--prefix`指定安裝前綴, 或設定`XDG_DATA_HOME`。
+hint-remove-file = 請檢查檔案所在目錄的權限。
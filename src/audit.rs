@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::messages as msg;
+use crate::manifest::{self, LockedPackage};
+
+// where `cargo audit` clones the advisory database by default.
+static DEFAULT_ADVISORY_DB_LOC: &str = ".cargo/advisory-db";
+static CRATES_IO_SOURCE: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+#[derive(Deserialize, Debug)]
+struct AdvisoryMetadata {
+    id: String,
+    package: String,
+    // `unmaintained`, `unsound`, `notice`, the advisory isn't a vulnerability when set.
+    informational: Option<String>,
+    withdrawn: Option<toml::Value>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct AdvisoryVersions {
+    #[serde(default)]
+    patched: Vec<String>,
+    #[serde(default)]
+    unaffected: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AdvisoryFile {
+    advisory: AdvisoryMetadata,
+    #[serde(default)]
+    versions: AdvisoryVersions,
+}
+
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    pub title: String,
+    pub informational: Option<String>,
+    pub patched: Vec<VersionReq>,
+    pub unaffected: Vec<VersionReq>,
+}
+
+impl Advisory {
+    pub fn affects(&self, version: &Version) -> bool {
+        !self.patched.iter().any(|a| a.matches(version))
+            && !self.unaffected.iter().any(|a| a.matches(version))
+    }
+}
+
+#[derive(Debug)]
+pub struct Finding {
+    pub package: String,
+    pub version: String,
+    pub advisory: Advisory,
+}
+
+#[derive(Debug, Default)]
+pub struct ProjectAudit {
+    pub vulnerabilities: Vec<Finding>,
+    pub informational: Vec<Finding>,
+    pub yanked: Vec<(String, String)>,
+}
+
+pub fn default_advisory_db() -> Option<PathBuf> {
+    env::home_dir().map(|mut a| {
+        a.extend(DEFAULT_ADVISORY_DB_LOC.split('/'));
+        a
+    })
+}
+
+/// Splits an advisory in the markdown format into its TOML front matter and its title.
+fn split_markdown_advisory(content: &str) -> Option<(&str, String)> {
+    let start = content.find("```toml")? + "```toml".len();
+    let rest = &content[start..];
+    let end = rest.find("```")?;
+    let front_matter = &rest[..end];
+    let title = rest[end + 3..]
+        .lines()
+        .find_map(|a| a.strip_prefix("# "))
+        .unwrap_or("")
+        .trim()
+        .to_owned();
+    Some((front_matter, title))
+}
+
+fn parse_advisory(path: &Path) -> Result<Option<Advisory>> {
+    let content = fs::read_to_string(path)
+        .context(ReadFileSnafu {
+            dynamic_errmsg: msg::read_file_failed(path.to_string_lossy().into_owned()),
+        })?;
+    let (front_matter, title) = match path.extension().and_then(|a| a.to_str()) {
+        Some("md") => {
+            match split_markdown_advisory(&content) {
+                Some((f, t)) => (f.to_owned(), t),
+                None => return Ok(None),
+            }
+        },
+        // the legacy advisory format is plain TOML.
+        Some("toml") => (content, String::new()),
+        _ => return Ok(None),
+    };
+
+    let file: AdvisoryFile = toml::from_str(&front_matter)
+        .context(ParseTomlSnafu {
+            dynamic_errmsg: msg::parse_toml_failed(path.to_string_lossy().into_owned()),
+        })?;
+    if file.advisory.withdrawn.is_some() {
+        return Ok(None);
+    }
+    let parse_reqs = |reqs: &[String]| {
+        reqs.iter()
+            .filter_map(|a| VersionReq::parse(a).ok())
+            .collect::<Vec<_>>()
+    };
+    Ok(Some(Advisory {
+        id: file.advisory.id,
+        package: file.advisory.package,
+        title,
+        informational: file.advisory.informational,
+        patched: parse_reqs(&file.versions.patched),
+        unaffected: parse_reqs(&file.versions.unaffected),
+    }))
+}
+
+/// Loads `crates/<name>/RUSTSEC-*.md` of a local advisory-db checkout, keyed by the crate name.
+pub fn load_advisory_db(db_dir: &Path) -> Result<HashMap<String, Vec<Advisory>>> {
+    let crates_dir = db_dir.join("crates");
+    if !crates_dir.is_dir() {
+        return Err(NotFoundAdvisoryDbSnafu {
+            db_location: db_dir.to_string_lossy().into_owned()
+        }.build());
+    }
+
+    let mut advisories = HashMap::<String, Vec<Advisory>>::new();
+    let read_dir_of = |dir: &Path| {
+        fs::read_dir(dir)
+            .context(ReadDirSnafu {
+                dynamic_errmsg: msg::read_directory_failed(dir.to_string_lossy().into_owned())
+            })
+    };
+    for crate_dir in read_dir_of(&crates_dir)? {
+        let crate_dir = crate_dir.context(DirEntrySnafu {
+            dynamic_errmsg: msg::read_directory_entry_failed()
+        })?.path();
+        if !crate_dir.is_dir() {
+            continue;
+        }
+        for advisory_file in read_dir_of(&crate_dir)? {
+            let advisory_file = advisory_file.context(DirEntrySnafu {
+                dynamic_errmsg: msg::read_directory_entry_failed()
+            })?.path();
+            match parse_advisory(&advisory_file) {
+                Ok(Some(advisory)) => {
+                    advisories.entry(advisory.package.clone())
+                        .or_default()
+                        .push(advisory);
+                },
+                Ok(None) => {},
+                Err(e) => {
+                    // one broken advisory shouldn't stop the audit.
+                    eprintln!("{}", msg::audit_skip_advisory(advisory_file.to_string_lossy().into_owned()));
+                    e.report_warning();
+                }
+            }
+        }
+    }
+    Ok(advisories)
+}
+
+/// Reads the yanked flags from the sparse index cache cargo keeps under `CARGO_HOME`,
+/// so the check works offline. Crates never fetched are unknown.
+pub struct YankedIndex {
+    cache_dirs: Vec<PathBuf>,
+    // crate name -> version -> yanked
+    known: HashMap<String, HashMap<String, bool>>,
+}
+
+impl YankedIndex {
+    pub fn new() -> Self {
+        let cargo_home = env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::home_dir().map(|a| a.join(".cargo")));
+        let mut cache_dirs = vec![];
+        if let Some(index_dir) = cargo_home.map(|a| a.join("registry").join("index"))
+            && let Ok(read_dir) = fs::read_dir(index_dir) {
+                for ent in read_dir.flatten() {
+                    let cache_dir = ent.path().join(".cache");
+                    if cache_dir.is_dir() {
+                        cache_dirs.push(cache_dir);
+                    }
+                }
+        }
+        Self {
+            cache_dirs,
+            known: HashMap::new(),
+        }
+    }
+
+    fn index_relative_path(name: &str) -> PathBuf {
+        let name = name.to_lowercase();
+        match name.len() {
+            1 => PathBuf::from("1").join(&name),
+            2 => PathBuf::from("2").join(&name),
+            3 => PathBuf::from("3").join(&name[..1]).join(&name),
+            _ => PathBuf::from(&name[..2]).join(&name[2..4]).join(&name),
+        }
+    }
+
+    fn load(&self, name: &str) -> HashMap<String, bool> {
+        #[derive(Deserialize)]
+        struct IndexEntry {
+            vers: String,
+            #[serde(default)]
+            yanked: bool,
+        }
+
+        let mut versions = HashMap::new();
+        let rel = Self::index_relative_path(name);
+        for cache_dir in &self.cache_dirs {
+            let Ok(content) = fs::read(cache_dir.join(&rel)) else {
+                continue;
+            };
+            // the cache file is a header followed by NUL separated `version`, `json` pairs.
+            for chunk in content.split(|a| *a == 0) {
+                if chunk.first() != Some(&b'{') {
+                    continue;
+                }
+                if let Ok(entry) = serde_json::from_slice::<IndexEntry>(chunk) {
+                    versions.insert(entry.vers, entry.yanked);
+                }
+            }
+        }
+        versions
+    }
+
+    pub fn is_yanked(&mut self, name: &str, version: &str) -> Option<bool> {
+        if !self.known.contains_key(name) {
+            let versions = self.load(name);
+            self.known.insert(name.to_owned(), versions);
+        }
+        self.known.get(name)
+            .and_then(|a| a.get(version))
+            .copied()
+    }
+}
+
+pub fn audit_packages(packages: &[LockedPackage],
+    advisories: &HashMap<String, Vec<Advisory>>,
+    yanked_index: Option<&mut YankedIndex>) -> ProjectAudit {
+    let mut result = ProjectAudit::default();
+    for p in packages.iter().filter(|a| a.source.is_some()) {
+        let Ok(version) = Version::parse(&p.version) else { continue; };
+        for advisory in advisories.get(&p.name).into_iter().flatten() {
+            if !advisory.affects(&version) {
+                continue;
+            }
+            let finding = Finding {
+                package: p.name.clone(),
+                version: p.version.clone(),
+                advisory: advisory.clone(),
+            };
+            if advisory.informational.is_some() {
+                result.informational.push(finding);
+            } else {
+                result.vulnerabilities.push(finding);
+            }
+        }
+    }
+
+    if let Some(index) = yanked_index {
+        for p in packages.iter()
+            .filter(|a| a.source.as_deref() == Some(CRATES_IO_SOURCE)) {
+            if index.is_yanked(&p.name, &p.version) == Some(true) {
+                result.yanked.push((p.name.clone(), p.version.clone()));
+            }
+        }
+    }
+    result
+}
+
+fn print_finding(informational: bool, f: &Finding) {
+    let patched = f.advisory.patched.iter()
+        .map(|a| a.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let advisory_id = f.advisory.id.clone();
+    let crate_name = f.package.clone();
+    let version = f.version.clone();
+    let title = f.advisory.title.clone();
+    let patched = if patched.is_empty() { "-".to_owned() } else { patched };
+    if informational {
+        let kind = f.advisory.informational.clone().unwrap_or_default();
+        println!("  {}", msg::audit_informational(advisory_id, kind, crate_name, version, title));
+    } else {
+        println!("  {}", msg::audit_vulnerability(advisory_id, crate_name, version, title, patched));
+    }
+}
+
+/// Audits the `Cargo.lock` of every project, returns `true` when the run should fail.
+pub fn run_audit(projects: &[PathBuf], db_dir: &Path, check_yanked: bool, deny_warnings: bool) -> Result<bool> {
+    let advisories = load_advisory_db(db_dir)?;
+    let mut yanked_index = if check_yanked { Some(YankedIndex::new()) } else { None };
+
+    let (mut vulnerabilities, mut warnings, mut affected_projects) = (0usize, 0usize, 0usize);
+    for project in projects {
+        let Some(packages) = manifest::read_lockfile(project)? else {
+            continue;
+        };
+        let result = audit_packages(&packages, &advisories, yanked_index.as_mut());
+        if result.vulnerabilities.is_empty()
+            && result.informational.is_empty()
+            && result.yanked.is_empty() {
+            continue;
+        }
+
+        affected_projects += 1;
+        vulnerabilities += result.vulnerabilities.len();
+        warnings += result.informational.len() + result.yanked.len();
+
+        println!("# {}", project.to_string_lossy());
+        for f in &result.vulnerabilities {
+            print_finding(false, f);
+        }
+        for f in &result.informational {
+            print_finding(true, f);
+        }
+        for (name, version) in &result.yanked {
+            println!("  {}", msg::audit_yanked(name.clone(), version.clone()));
+        }
+    }
+
+    println!("{}", msg::audit_summary(vulnerabilities, warnings, affected_projects));
+    Ok(vulnerabilities > 0 || (deny_warnings && warnings > 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(patched: &[&str], unaffected: &[&str]) -> Advisory {
+        let reqs = |a: &[&str]| a.iter().map(|b| VersionReq::parse(b).unwrap()).collect();
+        Advisory {
+            id: "RUSTSEC-2020-0001".to_owned(),
+            package: "a".to_owned(),
+            title: String::new(),
+            informational: None,
+            patched: reqs(patched),
+            unaffected: reqs(unaffected),
+        }
+    }
+
+    #[test]
+    fn affects_outside_the_patched_and_unaffected() {
+        let a = advisory(&[">= 1.2.3, < 2.0.0", ">= 2.1.0"], &["< 0.5.0"]);
+        let affects = |v| a.affects(&Version::parse(v).unwrap());
+        assert!(affects("1.2.2"));
+        assert!(affects("2.0.5"));
+        assert!(affects("0.5.0"));
+        assert!(!affects("1.2.3"));
+        assert!(!affects("2.1.0"));
+        assert!(!affects("0.4.9"));
+        // nothing patched, every version.
+        assert!(advisory(&[], &[]).affects(&Version::parse("9.9.9").unwrap()));
+    }
+
+    #[test]
+    fn split_markdown_advisory_front_matter() {
+        let content = "```toml\n[advisory]\nid = \"RUSTSEC-2020-0001\"\n```\n\n# Use after free in `a`\n\nDetails.\n";
+        let (front_matter, title) = split_markdown_advisory(content).unwrap();
+        assert_eq!(front_matter, "\n[advisory]\nid = \"RUSTSEC-2020-0001\"\n");
+        assert_eq!(title, "Use after free in `a`");
+
+        assert_eq!(split_markdown_advisory("```toml\n[advisory]\n```\n").unwrap().1, "");
+        assert!(split_markdown_advisory("# no front matter\n").is_none());
+        assert!(split_markdown_advisory("```toml\n[advisory]\n").is_none());
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::dep_graph::DependencyGraph;
+use crate::invocation::{self, CargoInvocation};
+
+// where the ninja file keeps the stamps of the finished projects, relative to the build dir.
+static NINJA_STAMP_DIR: &str = ".rust_recursive_action";
+
+/// FNV-1a, stable across runs and toolchains unlike the std hasher.
+fn stable_hash(s: &str) -> u32 {
+    let mut h: u32 = 0x811c9dc5;
+    for b in s.bytes() {
+        h ^= b as u32;
+        h = h.wrapping_mul(0x01000193);
+    }
+    h
+}
+
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|a| if a.is_ascii_alphanumeric() || a == '_' || a == '.' || a == '-' { a } else { '_' })
+        .collect()
+}
+
+/// Target names derived from the project paths relative to the root: `crates/foo` -> `crates-foo`.
+/// The names only hold `[A-Za-z0-9_.-]`, a short hash of the path is appended
+/// when two paths would give the same name.
+pub fn target_names(root: &Path, projects: &[PathBuf]) -> Vec<String> {
+    let rels = projects.iter()
+        .map(|p| {
+            let rel = p.strip_prefix(root).unwrap_or(p);
+            rel.components()
+                .map(|a| a.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect::<Vec<_>>();
+    let names = rels.iter()
+        .map(|rel| {
+            if rel.is_empty() {
+                "root".to_owned()
+            } else {
+                sanitize(&rel.replace('/', "-"))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut counts = HashMap::<&str, usize>::new();
+    for n in &names {
+        *counts.entry(n.as_str()).or_default() += 1;
+    }
+    // `all` is the aggregate target.
+    names.iter()
+        .zip(rels.iter())
+        .map(|(n, rel)| {
+            if counts[n.as_str()] > 1 || n == "all" {
+                format!("{}-{:08x}", n, stable_hash(rel))
+            } else {
+                n.clone()
+            }
+        })
+        .collect()
+}
+
+fn make_escape(s: &str) -> String {
+    s.replace('$', "$$")
+}
+
+fn ninja_escape(s: &str) -> String {
+    s.replace('$', "$$")
+}
+
+// a project run, there are as many runs of a project as the chosen toolchains.
+struct Target<'a> {
+    name: String,
+    deps: Vec<String>,
+    invocation: &'a CargoInvocation,
+}
+
+fn build_targets<'a>(root: &Path, graph: &DependencyGraph, order: &[usize],
+    invocations: &'a [Vec<CargoInvocation>]) -> Vec<Target<'a>> {
+    let names = target_names(root, &graph.projects);
+    let multiple = invocations.len() > 1;
+    let name_of = |i: usize, inv: &CargoInvocation| {
+        if multiple {
+            format!("{}--{}", names[i], sanitize(inv.toolchain.as_deref().unwrap_or("default")))
+        } else {
+            names[i].clone()
+        }
+    };
+
+    let mut targets = vec![];
+    for per_toolchain in invocations {
+        for &i in order {
+            let inv = &per_toolchain[i];
+            targets.push(Target {
+                name: name_of(i, inv),
+                deps: graph.dependencies[i].iter()
+                    .map(|&d| name_of(d, &per_toolchain[d]))
+                    .collect(),
+                invocation: inv,
+            });
+        }
+    }
+    targets
+}
+
+/// One phony target per project run depending on the targets of its path dependencies,
+/// `make -j` keeps the dependency order.
+pub fn render_makefile(root: &Path, graph: &DependencyGraph, order: &[usize],
+    invocations: &[Vec<CargoInvocation>]) -> String {
+    let targets = build_targets(root, graph, order, invocations);
+    let names = targets.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(" ");
+
+    let mut out = String::new();
+    out.push_str(&format!("# generated by {} for {}\n\n", env!("CARGO_PKG_NAME"), make_escape(&root.to_string_lossy())));
+    out.push_str(&format!(".PHONY: all {}\n\n", names));
+    out.push_str(&format!("all: {}\n\n", names));
+    for t in &targets {
+        out.push_str(&format!("{}:", t.name));
+        for d in &t.deps {
+            out.push_str(&format!(" {}", d));
+        }
+        out.push('\n');
+        out.push_str(&format!("\tcd {} && {}\n\n",
+            make_escape(&invocation::shell_quote(&t.invocation.dir.to_string_lossy())),
+            make_escape(&t.invocation.command_line())));
+    }
+    out
+}
+
+/// The runs write stamps so a rerun of ninja skips the projects which already succeeded,
+/// every project also has a phony target of its name.
+pub fn render_ninja(root: &Path, graph: &DependencyGraph, order: &[usize],
+    invocations: &[Vec<CargoInvocation>]) -> String {
+    let targets = build_targets(root, graph, order, invocations);
+    let stamp = |name: &str| format!("{}/{}.stamp", NINJA_STAMP_DIR, name);
+
+    let mut out = String::new();
+    out.push_str(&format!("# generated by {} for {}\n\n", env!("CARGO_PKG_NAME"), ninja_escape(&root.to_string_lossy())));
+    out.push_str("rule cargo\n");
+    out.push_str("  command = (cd $dir && $cmd) && mkdir -p $$(dirname $out) && touch $out\n");
+    out.push_str("  description = $name\n\n");
+    for t in &targets {
+        out.push_str(&format!("build {}: cargo", stamp(&t.name)));
+        if !t.deps.is_empty() {
+            out.push_str(" |");
+            for d in &t.deps {
+                out.push_str(&format!(" {}", stamp(d)));
+            }
+        }
+        out.push('\n');
+        out.push_str(&format!("  dir = {}\n", ninja_escape(&invocation::shell_quote(&t.invocation.dir.to_string_lossy()))));
+        out.push_str(&format!("  cmd = {}\n", ninja_escape(&t.invocation.command_line())));
+        out.push_str(&format!("  name = {}\n", t.name));
+        out.push_str(&format!("build {}: phony {}\n\n", t.name, stamp(&t.name)));
+    }
+    out.push_str(&format!("build all: phony {}\n",
+        targets.iter().map(|a| stamp(&a.name)).collect::<Vec<_>>().join(" ")));
+    out.push_str("default all\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(projects: &[&str]) -> Vec<String> {
+        let projects = projects.iter().map(|a| Path::new("/r").join(a)).collect::<Vec<_>>();
+        target_names(Path::new("/r"), &projects)
+    }
+
+    #[test]
+    fn target_names_from_the_relative_paths() {
+        assert_eq!(names(&["", "crates/foo", "bar baz", "é$x"]), ["root", "crates-foo", "bar_baz", "__x"]);
+    }
+
+    #[test]
+    fn target_names_told_apart_by_a_hash() {
+        let n = names(&["a-b", "a/b", "all", "c"]);
+        assert_eq!(n[0], format!("a-b-{:08x}", stable_hash("a-b")));
+        assert_eq!(n[1], format!("a-b-{:08x}", stable_hash("a/b")));
+        assert_ne!(n[0], n[1]);
+        assert_eq!(n[2], format!("all-{:08x}", stable_hash("all")));
+        assert_eq!(n[3], "c");
+    }
+
+    #[test]
+    fn stable_hash_is_fnv_1a() {
+        assert_eq!(stable_hash(""), 0x811c9dc5);
+        assert_eq!(stable_hash("a"), 0xe40c292c);
+    }
+}
@@ -0,0 +1,285 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use clap::{Args, ValueEnum};
+use snafu::prelude::*;
+
+use crate::GeneratingType;
+use crate::errors::*;
+use crate::invocation;
+use crate::manifest;
+use crate::messages as msg;
+
+/// The options of `--gs clean`, passed to `cargo clean` or used by `--clean-only`.
+#[derive(Args, Debug, Clone, Default)]
+pub struct CleanOptions {
+    #[arg(long, conflicts_with = "profile", help = msg::clean_release_helper())]
+    pub release: bool,
+
+    #[arg(long, value_name = "PROFILE", help = msg::clean_profile_helper())]
+    pub profile: Option<String>,
+
+    #[arg(long, value_name = "TRIPLE", help = msg::clean_target_helper())]
+    pub target: Vec<String>,
+
+    #[arg(long, conflicts_with = "clean_only", help = msg::clean_doc_helper())]
+    pub doc: bool,
+
+    #[arg(long, value_enum, value_name = "KIND", help = msg::clean_only_helper())]
+    pub clean_only: Option<CleanOnly>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CleanOnly {
+    #[value(help = msg::clean_only_incremental_helper())]
+    Incremental,
+    #[value(help = msg::clean_only_stale_deps_helper())]
+    StaleDeps,
+}
+
+impl CleanOptions {
+    pub fn is_active(&self) -> bool {
+        self.release
+            || self.profile.is_some()
+            || !self.target.is_empty()
+            || self.doc
+            || self.clean_only.is_some()
+    }
+
+    /// The arguments of `cargo clean` after the subcommand.
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.release {
+            args.push("--release".to_owned());
+        }
+        if let Some(p) = &self.profile {
+            args.extend(["--profile".to_owned(), p.clone()]);
+        }
+        for t in &self.target {
+            args.extend(["--target".to_owned(), t.clone()]);
+        }
+        if self.doc {
+            args.push("--doc".to_owned());
+        }
+        args
+    }
+
+    // the directory of the profile under `target`, `None` for all of them.
+    fn profile_dir_name(&self) -> Option<&str> {
+        if self.release {
+            return Some("release");
+        }
+        // like cargo, `dev` and `test` write to `debug` and `bench` to `release`.
+        self.profile.as_deref().map(|a| match a {
+            "dev" | "test" => "debug",
+            "bench" => "release",
+            _ => a,
+        })
+    }
+}
+
+/// The `target` of the runs in the project: `CARGO_TARGET_DIR` of `--env` or of this
+/// process, relative to the project where cargo runs, else the one of the workspace root.
+pub fn target_dir(project: &Path, envs: &[(String, String)]) -> PathBuf {
+    let from_env = envs.iter()
+        .rev()
+        .find(|(k, _)| k == "CARGO_TARGET_DIR")
+        .map(|(_, v)| v.clone())
+        .or_else(|| env::var("CARGO_TARGET_DIR").ok())
+        .filter(|a| !a.is_empty());
+    match from_env {
+        Some(d) => project.join(d),
+        None => manifest::workspace_root(project).join("target"),
+    }
+}
+
+// the directories like `target/debug` and `target/<triple>/release`.
+fn is_profile_dir(dir: &Path) -> bool {
+    dir.join("deps").is_dir() || dir.join(".fingerprint").is_dir()
+}
+
+fn sub_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else { return vec![]; };
+    let mut dirs = read_dir.flatten()
+        .map(|a| a.path())
+        .filter(|a| a.is_dir())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    dirs
+}
+
+// the profile directories of `target` the options select, of the host and of every
+// target triple unless `--target` names some.
+fn profile_dirs(target_dir: &Path, options: &CleanOptions) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    for d in sub_dirs(target_dir) {
+        if is_profile_dir(&d) {
+            if options.target.is_empty() {
+                dirs.push(d);
+            }
+            continue;
+        }
+        let triple = d.file_name().unwrap_or_default().to_string_lossy();
+        if !options.target.is_empty() && !options.target.iter().any(|a| *a == triple) {
+            continue;
+        }
+        dirs.extend(sub_dirs(&d).into_iter().filter(|a| is_profile_dir(a)));
+    }
+    match options.profile_dir_name() {
+        Some(name) => dirs.into_iter()
+            .filter(|a| a.file_name().is_some_and(|n| n == name))
+            .collect(),
+        None => dirs,
+    }
+}
+
+// the hash of the rustc which built a unit, and when.
+fn unit_rustc(unit_dir: &Path) -> Option<(u64, SystemTime)> {
+    let read_dir = fs::read_dir(unit_dir).ok()?;
+    for ent in read_dir.flatten() {
+        let path = ent.path();
+        if path.extension().is_none_or(|a| a != "json") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else { continue; };
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else { continue; };
+        let Some(rustc) = json.get("rustc").and_then(|a| a.as_u64()) else { continue; };
+        let modified = ent.metadata()
+            .and_then(|a| a.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        return Some((rustc, modified));
+    }
+    None
+}
+
+// the units built by another rustc than the latest one of the profile directory,
+// their fingerprints, build script outputs and artifacts named `<crate>-<hash>`.
+fn stale_artifacts(profile_dir: &Path) -> Vec<PathBuf> {
+    let units = sub_dirs(&profile_dir.join(".fingerprint"))
+        .into_iter()
+        .filter_map(|a| unit_rustc(&a).map(|r| (a, r)))
+        .collect::<Vec<_>>();
+    let Some(current) = units.iter().max_by_key(|(_, (_, modified))| *modified).map(|(_, (rustc, _))| *rustc) else {
+        return vec![];
+    };
+
+    let mut paths = vec![];
+    let mut hashes = vec![];
+    for (unit_dir, (rustc, _)) in &units {
+        if *rustc == current {
+            continue;
+        }
+        let name = unit_dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        if let Some((_, hash)) = name.rsplit_once('-') {
+            hashes.push(format!("-{hash}"));
+        }
+        paths.push(unit_dir.clone());
+        let build_dir = profile_dir.join("build").join(&name);
+        if build_dir.exists() {
+            paths.push(build_dir);
+        }
+    }
+    for dir in ["deps", "examples"] {
+        let Ok(read_dir) = fs::read_dir(profile_dir.join(dir)) else { continue; };
+        let mut artifacts = read_dir.flatten()
+            .map(|a| a.path())
+            .filter(|a| {
+                let name = a.file_name().unwrap_or_default().to_string_lossy();
+                hashes.iter().any(|h| name.contains(h.as_str()))
+            })
+            .collect::<Vec<_>>();
+        artifacts.sort();
+        paths.extend(artifacts);
+    }
+    paths
+}
+
+/// What `--clean-only` removes from a project, planned before the run so the
+/// bash commands and the dry run show the paths the subprocess mode removes.
+#[derive(Debug, Clone)]
+pub struct Prune {
+    pub project: PathBuf,
+    pub paths: Vec<PathBuf>,
+}
+
+impl Prune {
+    pub fn plan(project: &Path, only: CleanOnly, options: &CleanOptions, envs: &[(String, String)]) -> Self {
+        let target_dir = target_dir(project, envs);
+        let mut paths = vec![];
+        for d in profile_dirs(&target_dir, options) {
+            match only {
+                CleanOnly::Incremental => {
+                    let incremental = d.join("incremental");
+                    if incremental.is_dir() {
+                        paths.push(incremental);
+                    }
+                },
+                CleanOnly::StaleDeps => paths.extend(stale_artifacts(&d)),
+            }
+        }
+        Self {
+            project: project.to_path_buf(),
+            paths,
+        }
+    }
+
+    /// `rm -rf -- 'target/debug/incremental'`, quoted for a POSIX shell.
+    pub fn command_line(&self) -> String {
+        let mut words = vec!["rm".to_owned(), "-rf".to_owned(), "--".to_owned()];
+        words.extend(self.paths.iter().map(|a| invocation::shell_quote(&a.to_string_lossy())));
+        words.join(" ")
+    }
+
+    fn remove(&self) -> Result<()> {
+        for p in &self.paths {
+            let removed = if p.is_dir() {
+                fs::remove_dir_all(p)
+            } else {
+                fs::remove_file(p)
+            };
+            match removed {
+                Ok(()) => {},
+                // like `rm -f`, the run of another toolchain may have removed it.
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+                Err(e) => {
+                    return Err(e).context(RemoveFileSnafu {
+                        dynamic_errmsg: msg::remove_file_failed(p.to_string_lossy().into_owned()),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Like `process_dir` for the cargo runs, the removal is done by this program.
+    pub fn run(&self, ge_ty: GeneratingType) -> Result<()> {
+        let project = self.project.to_string_lossy().into_owned();
+        match ge_ty {
+            GeneratingType::BashCommands => {
+                if self.paths.is_empty() {
+                    println!("# {}", msg::clean_only_nothing(project));
+                } else {
+                    println!("{}", self.command_line());
+                }
+                Ok(())
+            },
+            GeneratingType::RunAsSubprocess => self.remove(),
+            GeneratingType::DryRunDebug => {
+                if self.paths.is_empty() {
+                    eprintln!("{}", msg::clean_only_nothing(project));
+                }
+                for p in &self.paths {
+                    eprintln!("REMOVE: {}", p.to_string_lossy());
+                }
+                Ok(())
+            },
+            GeneratingType::Makefile | GeneratingType::Ninja => {
+                // refused before the run, see `CleanOnlyInBuildFile`.
+                Ok(())
+            }
+        }
+    }
+}
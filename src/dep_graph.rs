@@ -0,0 +1,160 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::errors::*;
+use crate::manifest;
+
+/// The `path = "../x"` dependencies among the discovered projects,
+/// the projects are indexed by their position in the discovered list.
+#[derive(Debug)]
+pub struct DependencyGraph {
+    pub projects: Vec<PathBuf>,
+    // index -> the indexes it depends on
+    pub dependencies: Vec<BTreeSet<usize>>,
+    // index -> the indexes depending on it
+    pub dependents: Vec<BTreeSet<usize>>,
+}
+
+impl DependencyGraph {
+    /// Builds the graph from the manifests, a manifest which can't be read
+    /// contributes no edges and is left for cargo to complain about.
+    /// The dev-dependencies are left out, cargo allows the cycles through them.
+    pub fn build(projects: Vec<PathBuf>) -> Self {
+        let index_of = projects.iter()
+            .enumerate()
+            .filter_map(|(i, p)| fs::canonicalize(p).ok().map(|a| (a, i)))
+            .collect::<HashMap<PathBuf, usize>>();
+
+        let mut dependencies = vec![BTreeSet::new(); projects.len()];
+        let mut dependents = vec![BTreeSet::new(); projects.len()];
+        for (i, project) in projects.iter().enumerate() {
+            let Ok(m) = manifest::read_manifest(project) else { continue; };
+            for dep in manifest::declared_dependencies(&m) {
+                if dep.dev {
+                    continue;
+                }
+                let Some(dep_path) = dep.path else { continue; };
+                let Ok(dep_dir) = fs::canonicalize(project.join(dep_path)) else { continue; };
+                if let Some(&j) = index_of.get(&dep_dir)
+                    && j != i {
+                    dependencies[i].insert(j);
+                    dependents[j].insert(i);
+                }
+            }
+        }
+        Self { projects, dependencies, dependents }
+    }
+
+    /// Drops the edges, for an action which runs in the discovered order despite a cycle.
+    pub fn clear_dependencies(&mut self) {
+        self.dependencies.iter_mut().for_each(|a| a.clear());
+        self.dependents.iter_mut().for_each(|a| a.clear());
+    }
+
+    /// Kahn's algorithm, ties keep the discovered order so the runs are reproducible.
+    pub fn topological_order(&self) -> Result<Vec<usize>> {
+        let mut remaining = self.dependencies.iter()
+            .map(|a| a.len())
+            .collect::<Vec<_>>();
+        let mut ready = remaining.iter()
+            .enumerate()
+            .filter(|(_, n)| **n == 0)
+            .map(|(i, _)| i)
+            .collect::<BTreeSet<usize>>();
+
+        let mut order = Vec::with_capacity(self.projects.len());
+        while let Some(i) = ready.pop_first() {
+            order.push(i);
+            for &d in &self.dependents[i] {
+                remaining[d] -= 1;
+                if remaining[d] == 0 {
+                    ready.insert(d);
+                }
+            }
+        }
+
+        if order.len() != self.projects.len() {
+            return Err(DependencyCycleSnafu {
+                cycle: self.find_cycle(&remaining)
+                    .into_iter()
+                    .map(|a| self.projects[a].to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+            }.build());
+        }
+        Ok(order)
+    }
+
+    // walks the dependencies among the unsorted projects until one repeats.
+    fn find_cycle(&self, remaining: &[usize]) -> Vec<usize> {
+        let Some(start) = remaining.iter().position(|a| *a > 0) else {
+            return vec![];
+        };
+        let mut path = vec![start];
+        let mut current = start;
+        loop {
+            let Some(&next) = self.dependencies[current]
+                .iter()
+                .find(|a| remaining[**a] > 0) else {
+                return path;
+            };
+            if let Some(pos) = path.iter().position(|a| *a == next) {
+                let mut cycle = path.split_off(pos);
+                cycle.push(next);
+                return cycle;
+            }
+            path.push(next);
+            current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `(a, b)` is `a` depending on `b`.
+    fn graph(n: usize, edges: &[(usize, usize)]) -> DependencyGraph {
+        let mut dependencies = vec![BTreeSet::new(); n];
+        let mut dependents = vec![BTreeSet::new(); n];
+        for &(a, b) in edges {
+            dependencies[a].insert(b);
+            dependents[b].insert(a);
+        }
+        DependencyGraph {
+            projects: (0..n).map(|a| PathBuf::from(format!("p{a}"))).collect(),
+            dependencies,
+            dependents,
+        }
+    }
+
+    #[test]
+    fn topological_order_keeps_the_discovered_order() {
+        assert_eq!(graph(3, &[]).topological_order().unwrap(), vec![0, 1, 2]);
+        assert_eq!(graph(4, &[(0, 3), (1, 3), (3, 2)]).topological_order().unwrap(), vec![2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn topological_order_reports_the_cycle() {
+        let g = graph(4, &[(0, 1), (1, 2), (2, 1), (3, 0)]);
+        match g.topological_order() {
+            Err(Error::DependencyCycle { cycle, .. }) => assert_eq!(cycle, ["p1", "p2", "p1"]),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn find_cycle_skips_the_sorted_projects() {
+        let g = graph(3, &[(0, 1), (1, 2), (2, 1)]);
+        assert_eq!(g.find_cycle(&[1, 1, 1]), vec![1, 2, 1]);
+        assert_eq!(g.find_cycle(&[0, 0, 0]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn clear_dependencies_drops_the_cycle() {
+        let mut g = graph(2, &[(0, 1), (1, 0)]);
+        assert!(g.topological_order().is_err());
+        g.clear_dependencies();
+        assert_eq!(g.topological_order().unwrap(), vec![0, 1]);
+    }
+}
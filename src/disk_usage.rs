@@ -0,0 +1,36 @@
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DiskUsage {
+    pub bytes: u64,
+    // the newest modification time of the files under the directory.
+    pub last_modified: Option<SystemTime>,
+}
+
+/// Walks the directory without following symlinks, unreadable entries are ignored
+/// since the usage is only informative.
+pub fn disk_usage(dir: &Path) -> DiskUsage {
+    let mut usage = DiskUsage::default();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(d) = stack.pop() {
+        let Ok(read_dir) = fs::read_dir(&d) else { continue; };
+        for ent in read_dir.flatten() {
+            let Ok(metadata) = ent.metadata() else { continue; };
+            if metadata.is_dir() {
+                stack.push(ent.path());
+                continue;
+            }
+            usage.bytes += metadata.len();
+            if let Ok(modified) = metadata.modified() {
+                usage.last_modified = Some(match usage.last_modified {
+                    Some(t) if t > modified => t,
+                    _ => modified,
+                });
+            }
+        }
+    }
+    usage
+}
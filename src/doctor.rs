@@ -0,0 +1,132 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::invocation::CargoInvocation;
+use crate::language_helpers as lh;
+use crate::messages as msg;
+use crate::validation::{self, InvalidManifest};
+
+// the variables of cargo which change where the runs read and write.
+static CARGO_VARIABLES: [&str; 2] = ["CARGO_HOME", "CARGO_TARGET_DIR"];
+
+fn report_language() {
+    let diagnosis = lh::language_diagnosis();
+    if diagnosis.pseudo {
+        println!("{}", msg::doctor_language_pseudo(diagnosis.lang));
+        return;
+    }
+    match diagnosis.lang_dir {
+        Some((path, origin)) => {
+            println!("{}", msg::doctor_language_dir(path.to_string_lossy().into_owned(), lh::describe_origin(origin)));
+            println!("{}", msg::doctor_language(
+                diagnosis.lang,
+                diagnosis.desired_lang.unwrap_or_default(),
+                diagnosis.score.unwrap_or_default()));
+        },
+        None => {
+            println!("{}", msg::doctor_language_builtin(diagnosis.lang));
+        }
+    }
+}
+
+// the first line of `--version`, the reason when it can't be run.
+fn version_of(invocation: &CargoInvocation) -> Result<String, String> {
+    let output = invocation.command()
+        .output()
+        .map_err(|e| e.to_string())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or_default().trim().to_owned());
+    }
+    Ok(stdout.lines().next().unwrap_or_default().trim().to_owned())
+}
+
+// returns whether the program can be run.
+fn report_version(invocation: &CargoInvocation) -> bool {
+    match version_of(invocation) {
+        Ok(version) => {
+            println!("{}", msg::doctor_version(invocation.program.clone(), version));
+            true
+        },
+        Err(reason) => {
+            println!("{}", msg::doctor_command_failed(invocation.command_line(), reason));
+            false
+        }
+    }
+}
+
+// the `--env` pairs are what the runs get, over the ones of this process.
+fn report_cargo_variables(envs: &[(String, String)]) {
+    for name in CARGO_VARIABLES {
+        let value = envs.iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.clone())
+            .or_else(|| env::var(name).ok());
+        match value {
+            Some(v) => println!("{}", msg::doctor_variable_set(name, v)),
+            None => println!("{}", msg::doctor_variable_not_set(name)),
+        }
+    }
+}
+
+// a file is created and removed, the permission bits don't tell about
+// the read-only mounts and the ACLs.
+fn report_root_writable(root: &Path) -> bool {
+    let root_str = root.to_string_lossy().into_owned();
+    let probe = root.join(format!(".rust_recursively_action-doctor-{}", std::process::id()));
+    let created = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe);
+    match created {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            println!("{}", msg::doctor_root_writable(root_str));
+            true
+        },
+        Err(e) => {
+            println!("{}", msg::doctor_root_not_writable(root_str, e.to_string()));
+            false
+        }
+    }
+}
+
+// the ones left out by the discovery, `validate` tells where they break.
+fn report_manifests(discovered: usize, invalid: &[InvalidManifest]) {
+    for a in invalid {
+        println!("{}", msg::doctor_manifest_broken(
+            a.manifest_path().to_string_lossy().into_owned(),
+            validation::describe_problem(&a.problem)));
+    }
+    println!("{}", msg::validate_summary(discovered, invalid.len()));
+}
+
+/// `doctor`, the environment the runs would see, as one paste for a report.
+/// `cargo` is the `--version` run of the cargo the runs would use. Returns
+/// whether a problem is found: cargo can't be run, the root isn't writable,
+/// or a manifest is invalid.
+pub fn run_doctor(root: &Path, discovered: usize, invalid: &[InvalidManifest], cargo: &CargoInvocation) -> bool {
+    println!("{}", msg::doctor_program(env!("CARGO_PKG_VERSION")));
+    report_language();
+
+    let mut problems = 0;
+    if !report_version(cargo) {
+        problems += 1;
+    }
+    // only reported, cargo may be installed without rustup.
+    let rustup = CargoInvocation::new(root, "--version")
+        .program(Some("rustup"));
+    report_version(&rustup);
+    report_cargo_variables(&cargo.envs);
+
+    if !report_root_writable(root) {
+        problems += 1;
+    }
+    report_manifests(discovered, invalid);
+    problems += invalid.len();
+
+    println!("{}", msg::doctor_summary(problems));
+    problems > 0
+}
@@ -1,6 +1,8 @@
 
 use snafu::{Backtrace, Location, prelude::*};
 
+use crate::messages as msg;
+
 #[derive(Snafu, Debug)]
 #[snafu(visibility(pub))]
 pub(crate) enum Error {
@@ -53,6 +55,7 @@ pub(crate) enum Error {
         backtrace: Backtrace,
         #[snafu(implicit)]
         loc: Location,
+        path: String,
     },
     NotFoundLanguageFiles {
         backtrace: Backtrace,
@@ -64,6 +67,356 @@ pub(crate) enum Error {
         desired_dirname: String,
         available_langs: Vec<String>
     },
+    ReadFile {
+        source: std::io::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        dynamic_errmsg: String,
+    },
+    WriteFile {
+        source: std::io::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        dynamic_errmsg: String,
+    },
+    ParseToml {
+        #[snafu(source(from(toml::de::Error, Box::new)))]
+        source: Box<toml::de::Error>,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        dynamic_errmsg: String,
+    },
+    NotFoundAdvisoryDb {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        db_location: String,
+    },
+    StdIO {
+        source: std::io::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+    },
+    SelectionPrompt {
+        source: dialoguer::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+    },
+    DependencyCycle {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        cycle: Vec<String>,
+    },
+    StartGit {
+        source: std::io::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        dynamic_errmsg: String,
+    },
+    GitCommand {
+        args: Vec<String>,
+        dir_path: String,
+        stderr: String,
+    },
+    EnvFileLine {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        file_path: String,
+        line_number: usize,
+        line: String,
+    },
+    SerializeJson {
+        source: serde_json::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+    },
+    StartCargo {
+        source: std::io::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        dynamic_errmsg: String,
+    },
+    Metadata {
+        source: std::io::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        dynamic_errmsg: String,
+    },
+    NoDefaultAdvisoryDb {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+    },
+    ParseFluent {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        file_path: String,
+        errors: Vec<String>,
+    },
+    ParseLanguageId {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        lang: String,
+    },
+    RemoveFile {
+        source: std::io::Error,
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+        dynamic_errmsg: String,
+    },
+    NoDataDir {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+    },
+    CleanOnlyInBuildFile {
+        backtrace: Backtrace,
+        #[snafu(implicit)]
+        loc: Location,
+    },
+}
+
+// the backtraces are always captured, they're shown like the ones of std would be.
+fn backtrace_wanted(verbose: bool) -> bool {
+    verbose || ["RUST_LIB_BACKTRACE", "RUST_BACKTRACE"].iter()
+        .find_map(|a| std::env::var(a).ok())
+        .is_some_and(|a| a != "0")
+}
+
+impl Error {
+    /// The code of the variant, stable across the releases so it can be searched for.
+    /// The new variants take the next free number, the numbers are never reused.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Canonilizing { .. } => "RRA0001",
+            Error::AtleastOneInStack { .. } => "RRA0002",
+            Error::ReadDir { .. } => "RRA0003",
+            Error::DirEntry { .. } => "RRA0004",
+            Error::PathFileName { .. } => "RRA0005",
+            Error::CurrentDir { .. } => "RRA0006",
+            Error::ProcessExit { .. } => "RRA0007",
+            Error::LanguageIO { .. } => "RRA0008",
+            Error::NotFoundLanguageFiles { .. } => "RRA0009",
+            Error::LanguageNegotiated { .. } => "RRA0010",
+            Error::ReadFile { .. } => "RRA0011",
+            Error::WriteFile { .. } => "RRA0012",
+            Error::ParseToml { .. } => "RRA0013",
+            Error::NotFoundAdvisoryDb { .. } => "RRA0014",
+            Error::StdIO { .. } => "RRA0015",
+            Error::SelectionPrompt { .. } => "RRA0016",
+            Error::DependencyCycle { .. } => "RRA0017",
+            Error::StartGit { .. } => "RRA0018",
+            Error::GitCommand { .. } => "RRA0019",
+            Error::EnvFileLine { .. } => "RRA0020",
+            Error::SerializeJson { .. } => "RRA0021",
+            Error::StartCargo { .. } => "RRA0022",
+            Error::Metadata { .. } => "RRA0023",
+            Error::NoDefaultAdvisoryDb { .. } => "RRA0024",
+            Error::ParseFluent { .. } => "RRA0025",
+            Error::ParseLanguageId { .. } => "RRA0026",
+            Error::RemoveFile { .. } => "RRA0027",
+            Error::NoDataDir { .. } => "RRA0028",
+            Error::CleanOnlyInBuildFile { .. } => "RRA0029",
+        }
+    }
+
+    /// The localized message, the `dynamic_errmsg` is already localized where it's built.
+    pub fn message(&self) -> String {
+        match self {
+            Error::Canonilizing { dynamic_errmsg, .. }
+            | Error::AtleastOneInStack { dynamic_errmsg, .. }
+            | Error::ReadDir { dynamic_errmsg, .. }
+            | Error::DirEntry { dynamic_errmsg, .. }
+            | Error::PathFileName { dynamic_errmsg, .. }
+            | Error::ReadFile { dynamic_errmsg, .. }
+            | Error::WriteFile { dynamic_errmsg, .. }
+            | Error::RemoveFile { dynamic_errmsg, .. }
+            | Error::ParseToml { dynamic_errmsg, .. }
+            | Error::StartGit { dynamic_errmsg, .. }
+            | Error::StartCargo { dynamic_errmsg, .. }
+            | Error::Metadata { dynamic_errmsg, .. } => {
+                dynamic_errmsg.clone()
+            },
+            Error::CurrentDir { .. } => {
+                msg::error_current_dir()
+            },
+            Error::ProcessExit { code, .. } => {
+                match code {
+                    Some(c) => msg::error_process_exit(*c),
+                    None => msg::error_process_killed(),
+                }
+            },
+            Error::LanguageIO { path, .. } => {
+                msg::error_language_io(path.clone())
+            },
+            Error::NotFoundLanguageFiles { file_location, .. } => {
+                msg::error_language_files_not_found(file_location.clone())
+            },
+            Error::LanguageNegotiated { desired_dirname, .. } => {
+                msg::error_language_negotiated(desired_dirname.clone())
+            },
+            Error::NotFoundAdvisoryDb { db_location, .. } => {
+                msg::error_advisory_db_not_found(db_location.clone())
+            },
+            Error::StdIO { .. } => {
+                msg::error_std_io()
+            },
+            Error::SelectionPrompt { .. } => {
+                msg::error_selection_prompt()
+            },
+            Error::DependencyCycle { cycle, .. } => {
+                msg::error_dependency_cycle(cycle.join(" -> "))
+            },
+            Error::GitCommand { args, dir_path, .. } => {
+                msg::error_git_command(args.join(" "), dir_path.clone())
+            },
+            Error::EnvFileLine { file_path, line_number, line, .. } => {
+                msg::error_env_file_line(*line_number, file_path.clone(), line.clone())
+            },
+            Error::SerializeJson { .. } => {
+                msg::error_serialize_json()
+            },
+            Error::NoDefaultAdvisoryDb { .. } => {
+                msg::audit_no_default_db()
+            },
+            Error::ParseFluent { file_path, errors, .. } => {
+                msg::error_parse_fluent(file_path.clone(), errors.join("; "))
+            },
+            Error::ParseLanguageId { lang, .. } => {
+                msg::error_parse_language_id(lang.clone())
+            },
+            Error::NoDataDir { .. } => {
+                msg::error_no_data_dir()
+            },
+            Error::CleanOnlyInBuildFile { .. } => {
+                msg::error_clean_only_in_build_file()
+            },
+        }
+    }
+
+    /// What the user can do about the error.
+    pub fn hint(&self) -> String {
+        match self {
+            Error::Canonilizing { .. } => msg::hint_canonicalizing(),
+            Error::AtleastOneInStack { .. } | Error::SerializeJson { .. } => msg::hint_report_bug(),
+            Error::ReadDir { .. } | Error::DirEntry { .. } => msg::hint_read_dir(),
+            Error::PathFileName { .. } => msg::hint_path_file_name(),
+            Error::CurrentDir { .. } => msg::hint_current_dir(),
+            Error::ProcessExit { .. } => msg::hint_process_exit(),
+            Error::LanguageIO { .. } | Error::NotFoundLanguageFiles { .. } => msg::hint_language_files(),
+            Error::LanguageNegotiated { .. } | Error::ParseLanguageId { .. } => msg::hint_language_negotiated(),
+            Error::ParseFluent { .. } => msg::hint_parse_fluent(),
+            Error::ReadFile { .. } => msg::hint_read_file(),
+            Error::WriteFile { .. } => msg::hint_write_file(),
+            Error::ParseToml { .. } => msg::hint_parse_toml(),
+            Error::NotFoundAdvisoryDb { .. } | Error::NoDefaultAdvisoryDb { .. } => msg::hint_advisory_db(),
+            Error::StdIO { .. } => msg::hint_std_io(),
+            Error::SelectionPrompt { .. } => msg::hint_selection_prompt(),
+            Error::DependencyCycle { .. } => msg::hint_dependency_cycle(),
+            Error::StartGit { .. } => msg::hint_start_git(),
+            Error::GitCommand { .. } => msg::hint_git_command(),
+            Error::EnvFileLine { .. } => msg::hint_env_file_line(),
+            Error::StartCargo { .. } => msg::hint_start_cargo(),
+            Error::Metadata { .. } => msg::hint_metadata(),
+            Error::RemoveFile { .. } => msg::hint_remove_file(),
+            Error::NoDataDir { .. } => msg::hint_no_data_dir(),
+            Error::CleanOnlyInBuildFile { .. } => msg::hint_clean_only_in_build_file(),
+        }
+    }
+
+    // the variants without them are the ones kept small for `Result`.
+    fn location(&self) -> Option<(&Location, &Backtrace)> {
+        match self {
+            Error::Canonilizing { loc, backtrace, .. }
+            | Error::AtleastOneInStack { loc, backtrace, .. }
+            | Error::ReadDir { loc, backtrace, .. }
+            | Error::DirEntry { loc, backtrace, .. }
+            | Error::PathFileName { loc, backtrace, .. }
+            | Error::CurrentDir { loc, backtrace, .. }
+            | Error::LanguageIO { loc, backtrace, .. }
+            | Error::NotFoundLanguageFiles { loc, backtrace, .. }
+            | Error::ReadFile { loc, backtrace, .. }
+            | Error::WriteFile { loc, backtrace, .. }
+            | Error::ParseToml { loc, backtrace, .. }
+            | Error::NotFoundAdvisoryDb { loc, backtrace, .. }
+            | Error::StdIO { loc, backtrace, .. }
+            | Error::SelectionPrompt { loc, backtrace, .. }
+            | Error::DependencyCycle { loc, backtrace, .. }
+            | Error::StartGit { loc, backtrace, .. }
+            | Error::EnvFileLine { loc, backtrace, .. }
+            | Error::SerializeJson { loc, backtrace, .. }
+            | Error::StartCargo { loc, backtrace, .. }
+            | Error::Metadata { loc, backtrace, .. }
+            | Error::NoDefaultAdvisoryDb { loc, backtrace, .. }
+            | Error::ParseFluent { loc, backtrace, .. }
+            | Error::ParseLanguageId { loc, backtrace, .. }
+            | Error::RemoveFile { loc, backtrace, .. }
+            | Error::NoDataDir { loc, backtrace, .. }
+            | Error::CleanOnlyInBuildFile { loc, backtrace, .. } => Some((loc, backtrace)),
+            Error::ProcessExit { .. }
+            | Error::LanguageNegotiated { .. }
+            | Error::GitCommand { .. } => None,
+        }
+    }
+
+    // the output of the failed commands, the causes the variants don't carry as a source.
+    fn output(&self) -> Option<String> {
+        match self {
+            Error::ProcessExit { stderr, .. } => Some(String::from_utf8_lossy(stderr).trim_end().to_owned()),
+            Error::GitCommand { stderr, .. } => Some(stderr.trim_end().to_owned()),
+            _ => None,
+        }
+            .filter(|a| !a.is_empty())
+    }
+
+    /// Prints the diagnostic to stderr: the code and the message, the causes, the hint,
+    /// then where it's raised and the backtrace when `verbose` or under `RUST_BACKTRACE`.
+    pub fn report(&self, verbose: bool) {
+        self.print_diagnostic("error", verbose, backtrace_wanted(verbose));
+    }
+
+    /// For the errors which are worked around, like the language files falling back to English.
+    pub fn report_warning(&self) {
+        self.print_diagnostic("warning", false, false);
+    }
+
+    fn print_diagnostic(&self, level: &str, verbose: bool, with_backtrace: bool) {
+        eprintln!("{}[{}]: {}", level, self.code(), self.message());
+
+        let caused_by = msg::error_caused_by();
+        let mut source = std::error::Error::source(self);
+        while let Some(s) = source {
+            eprintln!("  {}: {}", caused_by, s);
+            source = s.source();
+        }
+        if let Some(o) = self.output() {
+            for line in o.lines() {
+                eprintln!("  | {}", line);
+            }
+        }
+        eprintln!("  {}: {}", msg::error_hint(), self.hint());
+
+        if verbose && let Some((loc, _)) = self.location() {
+            eprintln!("  {}: {}", msg::error_location(), loc);
+        }
+        if with_backtrace && let Some((_, backtrace)) = self.location() {
+            eprintln!("{}:\n{}", msg::error_backtrace(), backtrace);
+        }
+    }
 }
 
 pub(crate) type Result<T> = std::result::Result<T, self::Error>;
@@ -0,0 +1,158 @@
+use fluent::concurrent::FluentBundle;
+use fluent::types::{FluentNumber, FluentNumberOptions, FluentNumberStyle};
+use fluent::{FluentArgs, FluentResource, FluentValue};
+use intl_memoizer::concurrent::IntlLangMemoizer;
+
+static SIZE_UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+/// Registers `NUMBER` and `BYTES`, and formats the numbers with the grouping.
+/// en-US, zh_CN and zh_TW share `,` for the groups and `.` for the fraction,
+/// the locale of the bundle isn't looked at.
+pub fn add_functions(bundle: &mut FluentBundle<FluentResource>) {
+    // the names are free in a new bundle.
+    let _ = bundle.add_function("NUMBER", number);
+    let _ = bundle.add_function("BYTES", bytes);
+    bundle.set_formatter(Some(format_value));
+}
+
+fn format_value(value: &FluentValue, _intls: &IntlLangMemoizer) -> Option<String> {
+    match value {
+        FluentValue::Number(n) => Some(format_number(n)),
+        _ => None,
+    }
+}
+
+// `NUMBER($count, minimumFractionDigits: 2)`, a string holding a number is taken too.
+fn number<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let n = match positional.first() {
+        Some(FluentValue::Number(n)) => n.clone(),
+        Some(FluentValue::String(s)) => match s.parse::<FluentNumber>() {
+            Ok(n) => n,
+            Err(_) => return FluentValue::Error,
+        },
+        _ => return FluentValue::Error,
+    };
+    let mut n = n;
+    n.options.merge(named);
+    FluentValue::Number(n)
+}
+
+// `BYTES($bytes)` as `12.4 GiB`, the options of `NUMBER` apply to the scaled value.
+fn bytes<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let Some(FluentValue::Number(n)) = positional.first() else {
+        return FluentValue::Error;
+    };
+    let mut size = n.value;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < SIZE_UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    let fraction_digits = if unit == 0 { 0 } else { 1 };
+    let mut options = FluentNumberOptions {
+        minimum_fraction_digits: Some(fraction_digits),
+        maximum_fraction_digits: Some(fraction_digits),
+        ..Default::default()
+    };
+    options.merge(named);
+    let scaled = format_number(&FluentNumber::new(size, options));
+    FluentValue::String(format!("{} {}", scaled, SIZE_UNITS[unit]).into())
+}
+
+/// The fraction digits, the integer digits and the grouping of the options,
+/// the significant digits and the currencies aren't supported.
+pub fn format_number(n: &FluentNumber) -> String {
+    let options = &n.options;
+    let (value, suffix) = match options.style {
+        FluentNumberStyle::Percent => (n.value * 100.0, "%"),
+        _ => (n.value, ""),
+    };
+    let min_fraction = options.minimum_fraction_digits.unwrap_or(0);
+    let s = match options.maximum_fraction_digits {
+        Some(max_fraction) => format!("{:.*}", max_fraction.max(min_fraction), value),
+        None => value.to_string(),
+    };
+
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(a) => ("-", a),
+        None => ("", s.as_str()),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    let mut fraction = fraction.trim_end_matches('0').to_owned();
+    while fraction.len() < min_fraction {
+        fraction.push('0');
+    }
+    let mut integer = integer.to_owned();
+    while integer.len() < options.minimum_integer_digits.unwrap_or(0) {
+        integer.insert(0, '0');
+    }
+    if options.use_grouping {
+        integer = group_digits(&integer);
+    }
+
+    if fraction.is_empty() {
+        format!("{sign}{integer}{suffix}")
+    } else {
+        format!("{sign}{integer}.{fraction}{suffix}")
+    }
+}
+
+// `1234567` as `1,234,567`
+fn group_digits(integer: &str) -> String {
+    let mut out = String::with_capacity(integer.len() + integer.len() / 3);
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unic_langid::langid;
+
+    fn format(message: &str, value: impl Into<FluentValue<'static>>) -> String {
+        let mut bundle = FluentBundle::new_concurrent(vec![langid!("en-US")]);
+        bundle.set_use_isolating(false);
+        add_functions(&mut bundle);
+        bundle.add_resource(FluentResource::try_new(format!("m = {message}\n")).unwrap()).unwrap();
+        let mut args = FluentArgs::new();
+        args.set("v", value);
+        let mut errors = vec![];
+        let pattern = bundle.get_message("m").unwrap().value().unwrap();
+        let s = bundle.format_pattern(pattern, Some(&args), &mut errors).into_owned();
+        assert!(errors.is_empty(), "{errors:?}");
+        s
+    }
+
+    #[test]
+    fn numbers_are_grouped() {
+        assert_eq!(format("{ $v }", 1234567), "1,234,567");
+        assert_eq!(format("{ $v }", -1234.5), "-1,234.5");
+        assert_eq!(format("{ $v }", 999), "999");
+    }
+
+    #[test]
+    fn number_options() {
+        assert_eq!(format("{ NUMBER($v, minimumFractionDigits: 2) }", 3), "3.00");
+        assert_eq!(format("{ NUMBER($v, maximumFractionDigits: 1) }", 2.46), "2.5");
+        assert_eq!(format("{ NUMBER($v, minimumIntegerDigits: 3) }", 7), "007");
+        assert_eq!(format("{ NUMBER($v, useGrouping: \"false\") }", 12345), "12345");
+        assert_eq!(format("{ NUMBER($v, style: \"percent\") }", 0.25), "25%");
+        // a string holding a number.
+        assert_eq!(format("{ NUMBER($v) }", "1000"), "1,000");
+    }
+
+    #[test]
+    fn bytes_scaled_to_the_unit() {
+        assert_eq!(format("{ BYTES($v) }", 0), "0 B");
+        assert_eq!(format("{ BYTES($v) }", 1023), "1,023 B");
+        assert_eq!(format("{ BYTES($v) }", 1536), "1.5 KiB");
+        assert_eq!(format("{ BYTES($v) }", 13_314_398_618_u64), "12.4 GiB");
+        assert_eq!(format("{ BYTES($v) }", 5 * 1024_u64.pow(5)), "5,120.0 TiB");
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use clap::Args;
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::messages as msg;
+use crate::manifest;
+
+#[derive(Args, Debug, Default, Clone)]
+pub struct GitFilter {
+    #[arg(long, conflicts_with = "git_clean_only",
+        help = msg::git_dirty_only_helper())]
+    pub git_dirty_only: bool,
+
+    #[arg(long, help = msg::git_clean_only_helper())]
+    pub git_clean_only: bool,
+
+    #[arg(long, help = msg::skip_untracked_repos_helper())]
+    pub skip_untracked_repos: bool,
+
+    #[arg(long, value_name = "REV", help = msg::changed_since_helper())]
+    pub changed_since: Option<String>,
+}
+
+impl GitFilter {
+    pub fn is_active(&self) -> bool {
+        self.git_dirty_only
+            || self.git_clean_only
+            || self.skip_untracked_repos
+            || self.changed_since.is_some()
+    }
+}
+
+/// The work tree root containing `dir`, found by looking for `.git` upward
+/// since the directory walker never enters dot-directories.
+pub fn find_enclosing_repo(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|a| a.join(".git").exists())
+        .map(|a| a.to_path_buf())
+}
+
+/// Runs git in `dir`, returns its stdout, a non-zero exit is an error.
+pub fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context(StartGitSnafu {
+            dynamic_errmsg: msg::start_git_failed(dir.to_string_lossy().into_owned()),
+        })?;
+    if !output.status.success() {
+        return Err(GitCommandSnafu {
+            args: args.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            dir_path: dir.to_string_lossy().into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }.build());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Any modified, staged or untracked file under the project dir.
+pub fn is_dirty(project: &Path) -> Result<bool> {
+    let status = git_output(project, &["status", "--porcelain", "--", "."])?;
+    Ok(!status.trim().is_empty())
+}
+
+pub fn is_manifest_tracked(project: &Path) -> bool {
+    git_output(project, &["ls-files", "--error-unmatch", "--", manifest::MANIFEST_FILE_NAME]).is_ok()
+}
+
+/// Files under the project dir changed since `rev`, committed or not, and the new untracked ones.
+pub fn changed_since(project: &Path, rev: &str) -> Result<bool> {
+    let diff = git_output(project, &["diff", "--name-only", rev, "--", "."])?;
+    if !diff.trim().is_empty() {
+        return Ok(true);
+    }
+    let untracked = git_output(project, &["ls-files", "--others", "--exclude-standard", "--", "."])?;
+    Ok(!untracked.trim().is_empty())
+}
+
+fn keep_project(project: &Path, filter: &GitFilter) -> Result<bool> {
+    if find_enclosing_repo(project).is_none() {
+        // a project out of any repository never matches a git filter,
+        // only called with an active one.
+        return Ok(false);
+    }
+    if filter.skip_untracked_repos && !is_manifest_tracked(project) {
+        return Ok(false);
+    }
+    if filter.git_dirty_only || filter.git_clean_only {
+        let dirty = is_dirty(project)?;
+        if (filter.git_dirty_only && !dirty) || (filter.git_clean_only && dirty) {
+            return Ok(false);
+        }
+    }
+    if let Some(rev) = &filter.changed_since
+        && !changed_since(project, rev)? {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+pub fn filter_projects(projects: Vec<PathBuf>, filter: &GitFilter) -> Result<Vec<PathBuf>> {
+    if !filter.is_active() {
+        return Ok(projects);
+    }
+    let mut kept = Vec::with_capacity(projects.len());
+    for p in projects {
+        if keep_project(&p, filter)? {
+            kept.push(p);
+        }
+    }
+    Ok(kept)
+}
@@ -0,0 +1,143 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::Command;
+use clap_complete::Shell;
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::language_helpers as lh;
+use crate::messages as msg;
+use crate::shell_integration;
+
+// the translations shipped with the program, installed without the source tree.
+static LANGUAGE_FILES: [(&str, &str); 3] = [
+    ("en-US", include_str!("../i18n/fluent/en-US/lang.ftl")),
+    ("zh_CN", include_str!("../i18n/fluent/zh_CN/lang.ftl")),
+    ("zh_TW", include_str!("../i18n/fluent/zh_TW/lang.ftl")),
+];
+static LANGUAGE_FILE_NAME: &str = "lang.ftl";
+
+/// The options of `install-data`.
+#[derive(Debug, Clone)]
+pub struct InstallData {
+    // `<prefix>/share` instead of `$XDG_DATA_HOME`
+    pub prefix: Option<PathBuf>,
+    pub uninstall: bool,
+    pub completions: bool,
+    pub man: bool,
+}
+
+enum DataFile {
+    Language(&'static str),
+    Completions(Shell),
+    Man,
+}
+
+fn data_dir(prefix: Option<&Path>) -> Result<PathBuf> {
+    match prefix {
+        Some(p) => Ok(std::path::absolute(p).unwrap_or_else(|_| p.to_path_buf()).join("share")),
+        None => lh::user_data_dir().context(NoDataDirSnafu),
+    }
+}
+
+// every file the options ask for, the uninstallation removes all of them.
+fn data_files(data_dir: &Path, bin_name: &str, options: &InstallData) -> Vec<(PathBuf, DataFile)> {
+    let lang_dir = lh::lang_install_dir(data_dir);
+    let mut files = LANGUAGE_FILES.iter()
+        .map(|(lang, content)| (lang_dir.join(lang).join(LANGUAGE_FILE_NAME), DataFile::Language(content)))
+        .collect::<Vec<_>>();
+    if options.completions || options.uninstall {
+        for shell in shell_integration::INSTALLED_SHELLS {
+            if let Some(path) = shell_integration::completions_path(data_dir, bin_name, shell) {
+                files.push((path, DataFile::Completions(shell)));
+            }
+        }
+    }
+    if options.man || options.uninstall {
+        files.push((shell_integration::man_path(data_dir, bin_name), DataFile::Man));
+    }
+    files
+}
+
+fn install(files: Vec<(PathBuf, DataFile)>, cmd: &Command) -> Result<()> {
+    for (path, file) in files {
+        let content = match file {
+            DataFile::Language(content) => content.as_bytes().to_vec(),
+            DataFile::Completions(shell) => shell_integration::render_completions(cmd.clone(), shell),
+            DataFile::Man => shell_integration::render_man(cmd.clone())?,
+        };
+        let path_str = path.to_string_lossy().into_owned();
+        if fs::read(&path).is_ok_and(|a| a == content) {
+            println!("{}", msg::install_data_unchanged(path_str));
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context(WriteFileSnafu {
+                    dynamic_errmsg: msg::create_directory_failed(parent.to_string_lossy().into_owned()),
+                })?;
+        }
+        fs::write(&path, &content)
+            .context(WriteFileSnafu {
+                dynamic_errmsg: msg::write_file_failed(path_str.clone()),
+            })?;
+        println!("{}", msg::install_data_installed(path_str));
+    }
+    Ok(())
+}
+
+fn uninstall(files: Vec<(PathBuf, DataFile)>, data_dir: &Path) -> Result<()> {
+    let mut removed = false;
+    for (path, _) in files {
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                removed = true;
+                println!("{}", msg::install_data_removed(path.to_string_lossy().into_owned()));
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+            Err(e) => {
+                return Err(e).context(RemoveFileSnafu {
+                    dynamic_errmsg: msg::remove_file_failed(path.to_string_lossy().into_owned()),
+                });
+            }
+        }
+    }
+
+    // the directories of the program, a directory still holding something is left.
+    let app_dir = lh::app_data_dir(data_dir);
+    let lang_dir = lh::lang_install_dir(data_dir);
+    for (lang, _) in &LANGUAGE_FILES {
+        let _ = fs::remove_dir(lang_dir.join(lang));
+    }
+    for d in lang_dir.ancestors().take_while(|a| a.starts_with(&app_dir)) {
+        let _ = fs::remove_dir(d);
+    }
+
+    if !removed {
+        println!("{}", msg::install_data_nothing_removed(data_dir.to_string_lossy().into_owned()));
+    }
+    Ok(())
+}
+
+/// Installs the language files into `$XDG_DATA_HOME` or `<prefix>/share`, with the
+/// completions and the man page of `cmd` when asked, or removes them all.
+pub fn run_install_data(options: &InstallData, cmd: Command) -> Result<()> {
+    let data_dir = data_dir(options.prefix.as_deref())?;
+    let files = data_files(&data_dir, cmd.get_name(), options);
+    if options.uninstall {
+        return uninstall(files, &data_dir);
+    }
+    install(files, &cmd)?;
+
+    // like a prefix the executable isn't installed under.
+    let lang_dir = lh::lang_install_dir(&data_dir);
+    let searched = lh::lang_dir_candidates(lh::DEFAULT_LANG_DIR)
+        .iter()
+        .any(|a| a.path.as_ref().is_some_and(|p| *p == lang_dir));
+    if !searched {
+        eprintln!("{}", msg::install_data_not_searched(lang_dir.to_string_lossy().into_owned()));
+    }
+    Ok(())
+}
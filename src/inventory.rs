@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::messages as msg;
+use crate::manifest;
+
+#[derive(PartialEq, Debug, Default, Clone, Copy, Eq, ValueEnum)]
+pub enum InventoryFormat {
+    #[default]
+    #[value(help = msg::inventory_csv_helper())]
+    Csv,
+    #[value(help = msg::inventory_json_helper())]
+    Json,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionOrigin {
+    // resolved in a `Cargo.lock`
+    Locked,
+    // only known from the requirement in a `Cargo.toml`, the project has no lock file.
+    Declared,
+}
+
+#[derive(Serialize, Debug)]
+pub struct VersionUsage {
+    pub version: String,
+    pub origin: VersionOrigin,
+    // the semver compatible series: `1`, `0.4`, `0.0.3`
+    pub series: Option<String>,
+    pub projects: BTreeSet<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CrateUsage {
+    pub name: String,
+    pub multi_major: bool,
+    pub versions: Vec<VersionUsage>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Inventory {
+    pub crates: Vec<CrateUsage>,
+}
+
+fn series_of(major: u64, minor: Option<u64>, patch: Option<u64>) -> String {
+    match (major, minor, patch) {
+        (0, Some(0), Some(patch)) => format!("0.0.{}", patch),
+        (0, Some(minor), _) => format!("0.{}", minor),
+        (major, _, _) => format!("{}", major),
+    }
+}
+
+/// The semver compatibility series of a version, versions in the same series
+/// can be unified by cargo.
+fn compatible_series(version: &str, origin: VersionOrigin) -> Option<String> {
+    match origin {
+        VersionOrigin::Locked => {
+            let v = semver::Version::parse(version).ok()?;
+            Some(series_of(v.major, Some(v.minor), Some(v.patch)))
+        },
+        VersionOrigin::Declared => {
+            // the series of a requirement is the one of its first comparator, `0.8` -> `0.8`
+            let req = semver::VersionReq::parse(version).ok()?;
+            let c = req.comparators.first()?;
+            Some(series_of(c.major, c.minor, c.patch))
+        }
+    }
+}
+
+pub fn collect_inventory(projects: &[PathBuf]) -> Result<Inventory> {
+    // crate name -> (version, origin) -> projects
+    let mut usages = BTreeMap::<String, BTreeMap<(String, VersionOrigin), BTreeSet<String>>>::new();
+
+    for project in projects {
+        let project_str = project.to_string_lossy().into_owned();
+        let manifest = manifest::read_manifest(project)?;
+        let local_names = manifest::declared_dependencies(&manifest)
+            .into_iter()
+            .filter(|a| a.path.is_some())
+            .map(|a| a.package)
+            .collect::<BTreeSet<String>>();
+
+        match manifest::read_lockfile(project)? {
+            Some(packages) => {
+                for p in packages.into_iter()
+                    .filter(|a| a.source.is_some()) {
+                    usages.entry(p.name)
+                        .or_default()
+                        .entry((p.version, VersionOrigin::Locked))
+                        .or_default()
+                        .insert(project_str.clone());
+                }
+            },
+            None => {
+                for d in manifest::declared_dependencies(&manifest) {
+                    let Some(req) = d.requirement else { continue; };
+                    if local_names.contains(&d.package) {
+                        continue;
+                    }
+                    usages.entry(d.package)
+                        .or_default()
+                        .entry((req, VersionOrigin::Declared))
+                        .or_default()
+                        .insert(project_str.clone());
+                }
+            }
+        }
+    }
+
+    let crates = usages.into_iter()
+        .map(|(name, versions)| {
+            let versions = versions.into_iter()
+                .map(|((version, origin), projects)| {
+                    let series = compatible_series(&version, origin);
+                    VersionUsage { version, origin, series, projects }
+                })
+                .collect::<Vec<_>>();
+            let series = versions.iter()
+                .filter_map(|a| a.series.as_ref())
+                .collect::<BTreeSet<_>>();
+            CrateUsage {
+                name,
+                multi_major: series.len() > 1,
+                versions,
+            }
+        })
+        .collect();
+
+    Ok(Inventory { crates })
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub fn render_csv(inventory: &Inventory) -> String {
+    let mut out = String::from("name,version,origin,series,multi_major,projects\n");
+    for c in &inventory.crates {
+        for v in &c.versions {
+            let origin = match v.origin {
+                VersionOrigin::Locked => "locked",
+                VersionOrigin::Declared => "declared",
+            };
+            let projects = v.projects.iter()
+                .map(|a| a.as_str())
+                .collect::<Vec<_>>()
+                .join(";");
+            let row = [
+                csv_field(&c.name),
+                csv_field(&v.version),
+                origin.to_owned(),
+                csv_field(v.series.as_deref().unwrap_or("")),
+                c.multi_major.to_string(),
+                csv_field(&projects),
+            ];
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+pub fn render_json(inventory: &Inventory) -> Result<String> {
+    serde_json::to_string_pretty(inventory)
+        .context(SerializeJsonSnafu)
+}
+
+pub fn run_inventory(projects: &[PathBuf], format: InventoryFormat, multi_major_only: bool) -> Result<()> {
+    let mut inventory = collect_inventory(projects)?;
+    if multi_major_only {
+        inventory.crates.retain(|a| a.multi_major);
+    }
+
+    // highlight on stderr so the machine readable output keeps clean.
+    for c in inventory.crates.iter().filter(|a| a.multi_major) {
+        let series = c.versions.iter()
+            .filter_map(|a| a.series.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!("{}", msg::inventory_multi_major(c.name.clone(), series));
+    }
+
+    match format {
+        InventoryFormat::Csv => {
+            print!("{}", render_csv(&inventory));
+        },
+        InventoryFormat::Json => {
+            println!("{}", render_json(&inventory)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compatible_series_of_the_locked_versions() {
+        let series = |v| compatible_series(v, VersionOrigin::Locked);
+        assert_eq!(series("1.2.3").as_deref(), Some("1"));
+        assert_eq!(series("0.4.17").as_deref(), Some("0.4"));
+        assert_eq!(series("0.0.3").as_deref(), Some("0.0.3"));
+        assert_eq!(series("2.0.0-rc.1").as_deref(), Some("2"));
+        assert_eq!(series("1.2"), None);
+    }
+
+    #[test]
+    fn compatible_series_of_the_requirements() {
+        let series = |v| compatible_series(v, VersionOrigin::Declared);
+        assert_eq!(series("1.0").as_deref(), Some("1"));
+        assert_eq!(series("^0.8.5").as_deref(), Some("0.8"));
+        assert_eq!(series("~0.0.2").as_deref(), Some("0.0.2"));
+        assert_eq!(series(">=0.3, <0.5").as_deref(), Some("0.3"));
+        assert_eq!(series("0").as_deref(), Some("0"));
+        assert_eq!(series("*"), None);
+        assert_eq!(series("not a version"), None);
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::messages as msg;
+
+pub static DEFAULT_CARGO_BIN: &str = "cargo";
+
+/// One cargo run in a project, the same value renders the bash commands,
+/// the dry-run lines and the subprocess, so what is shown is what runs.
+#[derive(Debug, Clone)]
+pub struct CargoInvocation {
+    pub dir: PathBuf,
+    // `cargo`, or a wrapper like `cross`
+    pub program: String,
+    pub envs: Vec<(String, String)>,
+    pub toolchain: Option<String>,
+    pub args: Vec<String>,
+}
+
+impl CargoInvocation {
+    pub fn new(dir: &Path, subcmd: &str) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            program: DEFAULT_CARGO_BIN.to_owned(),
+            envs: vec![],
+            toolchain: None,
+            args: vec![subcmd.to_owned()],
+        }
+    }
+
+    pub fn program(mut self, program: Option<&str>) -> Self {
+        self.program = program.unwrap_or(DEFAULT_CARGO_BIN).to_owned();
+        self
+    }
+
+    pub fn envs(mut self, envs: &[(String, String)]) -> Self {
+        self.envs = envs.to_vec();
+        self
+    }
+
+    pub fn toolchain(mut self, toolchain: Option<&str>) -> Self {
+        self.toolchain = toolchain.map(|a| a.to_owned());
+        self
+    }
+
+    /// Appended after the subcommand, like the `--release` of `cargo clean`.
+    pub fn args(mut self, args: &[String]) -> Self {
+        self.args.extend(args.iter().cloned());
+        self
+    }
+
+    /// The program arguments, with `+toolchain` first when one is chosen.
+    pub fn full_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(t) = &self.toolchain {
+            args.push(format!("+{t}"));
+        }
+        args.extend(self.args.iter().cloned());
+        args
+    }
+
+    pub fn command(&self) -> Command {
+        let mut c = Command::new(&self.program);
+        c.args(self.full_args())
+            .envs(self.envs.iter().map(|(k, v)| (k, v)))
+            .current_dir(&self.dir);
+        c
+    }
+
+    /// `RUSTFLAGS='-C debuginfo=0' cargo +nightly clean`, quoted for a POSIX shell.
+    pub fn command_line(&self) -> String {
+        let mut words = self.envs.iter()
+            .map(|(k, v)| format!("{}={}", k, shell_quote(v)))
+            .collect::<Vec<_>>();
+        words.push(shell_quote(&self.program));
+        words.extend(self.full_args().iter().map(|a| shell_quote(a)));
+        words.join(" ")
+    }
+}
+
+/// Quotes a word for a POSIX shell, the plain words are kept as they are.
+pub fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word.chars().all(|a| a.is_ascii_alphanumeric() || "-_./+=:,@%".contains(a));
+    if plain {
+        word.to_owned()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+// `[A-Za-z_][A-Za-z0-9_]*`, the names are written unquoted into the scripts and the build files.
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|a| a.is_ascii_alphabetic() || a == '_')
+        && chars.all(|a| a.is_ascii_alphanumeric() || a == '_')
+}
+
+/// The value parser of `--env KEY=VALUE`.
+pub fn parse_env_pair(pair: &str) -> std::result::Result<(String, String), String> {
+    match pair.split_once('=') {
+        Some((k, v)) if is_env_name(k.trim()) => Ok((k.trim().to_owned(), v.to_owned())),
+        Some((k, _)) if !k.trim().is_empty() => Err(msg::env_name_invalid(k.trim().to_owned())),
+        _ => Err(msg::env_pair_invalid(pair.to_owned())),
+    }
+}
+
+/// Reads `KEY=VALUE` lines, the blank lines, `#` comments, `export ` prefixes
+/// and the quotes around the values are allowed like in a dotenv file.
+pub fn read_env_file(file_path: &Path) -> Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(file_path)
+        .context(ReadFileSnafu {
+            dynamic_errmsg: msg::read_file_failed(file_path.to_string_lossy().into_owned()),
+        })?;
+
+    let mut envs = vec![];
+    for (n, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Ok((k, v)) = parse_env_pair(line) else {
+            return Err(EnvFileLineSnafu {
+                file_path: file_path.to_string_lossy().into_owned(),
+                line_number: n + 1,
+                line: line.to_owned(),
+            }.build());
+        };
+        let v = v.trim();
+        let v = [('"', '"'), ('\'', '\'')].iter()
+            .find_map(|(l, r)| v.strip_prefix(*l).and_then(|a| a.strip_suffix(*r)))
+            .unwrap_or(v);
+        envs.push((k, v.to_owned()));
+    }
+    Ok(envs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_quote_words() {
+        assert_eq!(shell_quote("cargo"), "cargo");
+        assert_eq!(shell_quote("/tmp/a-b_c.d+e=f:g,h@i%j"), "/tmp/a-b_c.d+e=f:g,h@i%j");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("$HOME;rm"), "'$HOME;rm'");
+    }
+
+    #[test]
+    fn parse_env_pair_names() {
+        assert_eq!(parse_env_pair("CARGO_TARGET_DIR=/tmp/t"), Ok(("CARGO_TARGET_DIR".to_owned(), "/tmp/t".to_owned())));
+        assert_eq!(parse_env_pair("_a1=x=y"), Ok(("_a1".to_owned(), "x=y".to_owned())));
+        assert_eq!(parse_env_pair("A="), Ok(("A".to_owned(), String::new())));
+        for pair in ["A", "=1", "A B=1", "1A=1", "X;rm -rf ~;Y=1", "A-B=1"] {
+            assert!(parse_env_pair(pair).is_err(), "{pair}");
+        }
+    }
+
+    #[test]
+    fn read_env_file_lines() {
+        let path = std::env::temp_dir().join(format!("rust_recursively_action-env-{}", std::process::id()));
+        fs::write(&path, "# a comment\n\nA=1\nexport B = \"two words\"\nC='$x'\nD=\"unbalanced\n").unwrap();
+        let envs = read_env_file(&path);
+        fs::write(&path, "A=1\nX;rm -rf ~;Y=1\n").unwrap();
+        let bad = read_env_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(envs.unwrap(), [("A", "1"), ("B", "two words"), ("C", "$x"), ("D", "\"unbalanced")]
+            .map(|(k, v)| (k.to_owned(), v.to_owned())));
+        assert!(matches!(bad, Err(Error::EnvFileLine { line_number: 2, .. })));
+    }
+}
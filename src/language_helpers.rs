@@ -1,17 +1,22 @@
 
-use fluent::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use fluent::concurrent::FluentBundle;
+use fluent::{FluentArgs, FluentResource, FluentValue};
 use unic_langid::LanguageIdentifier;
-use sys_locale;
 
-use std::{io, env};
-use std::path::PathBuf;
-use std::sync::{Mutex, Arc};
-use std::io::Read;
+use snafu::prelude::*;
+use unic_langid::langid;
+
+use std::borrow::Cow;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
 use std::fs;
 
 
-use snafu::prelude::*;
 use crate::errors::*;
+use crate::fluent_functions;
+use crate::messages as msg;
 
 pub fn language_matches_score(l1: &LanguageIdentifier, l2: &LanguageIdentifier) -> u8 {
     let mut base = 0u8;
@@ -30,8 +35,9 @@ struct LanguageDeductionHelperS {
     pub score: u8,
 }
 
-fn resolve_desired_lang(lang_name: Option<String>, lang_dir: &PathBuf)
-    -> Result<Vec<LanguageDeductionHelperS>> {
+// the name asked for, the system locale when none, and the languages by their scores.
+fn resolve_desired_lang(lang_name: Option<String>, lang_dir: &Path)
+    -> Result<(String, Vec<LanguageDeductionHelperS>)> {
         if !lang_dir.exists() || !lang_dir.is_dir() {
             return Err(NotFoundLanguageFilesSnafu {
                 file_location: lang_dir
@@ -43,31 +49,30 @@ fn resolve_desired_lang(lang_name: Option<String>, lang_dir: &PathBuf)
         let (desired_lang_identifier, desired_dirname) = match &lang_name {
             Some(lang) => {
                 (lang.parse::<LanguageIdentifier>()
-                    .expect(&format!("Parse {lang} as language identifier failed.")),
+                    .ok()
+                    .context(ParseLanguageIdSnafu { lang: lang.clone() })?,
                     lang.clone())
             },
             None => {
-                let n = sys_locale::get_locale()
-                    .expect(&format!("Get system locale failed."));
-                let li = n.clone()
-                    .parse::<LanguageIdentifier>()
-                    .expect("System's default locale parses failed.");
-                (li, n)
+                // no locale, or one like `C` which names no language, is taken as English.
+                sys_locale::get_locale()
+                    .and_then(|n| n.parse::<LanguageIdentifier>().ok().map(|li| (li, n)))
+                    .unwrap_or_else(|| (langid!("en-US"), BUILTIN_LANG.to_owned()))
             }
         };
 
         let available_langs = {
             let mut available_langs = Vec::new();
             let read_dir = fs::read_dir(lang_dir)
-                .expect(&format!("Read dir {:?} failed.", lang_dir));
+                .context(LanguageIOSnafu { path: lang_dir.to_string_lossy().into_owned() })?;
             for dir in read_dir {
-                let dir_ent = dir.expect(&format!("Read a dir entry in {:?} failed.", lang_dir));
+                let dir_ent = dir
+                    .context(LanguageIOSnafu { path: lang_dir.to_string_lossy().into_owned() })?;
                 let dir_path = dir_ent.path();
 
-                let dirname = {
-                    let os_name = dir_ent.file_name();
-                    os_name.to_str()
-                        .expect(&format!("OsString {:?} converts to String failed.", &os_name)).to_owned()
+                // the names which aren't UTF-8 can't be language identifiers either.
+                let Some(dirname) = dir_ent.file_name().to_str().map(|a| a.to_owned()) else {
+                    continue;
                 };
                 match &dirname.parse::<LanguageIdentifier>() {
                     Ok(id) => {
@@ -75,7 +80,7 @@ fn resolve_desired_lang(lang_name: Option<String>, lang_dir: &PathBuf)
                             lid: id.clone(),
                             lang_name: dirname,
                             dir_path,
-                            score: language_matches_score(&id, &desired_lang_identifier)
+                            score: language_matches_score(id, &desired_lang_identifier)
                         };
                         available_langs.push(tmp);
                     },
@@ -88,7 +93,7 @@ fn resolve_desired_lang(lang_name: Option<String>, lang_dir: &PathBuf)
             available_langs
         };
         if !available_langs.is_empty() {
-            Ok(available_langs)
+            Ok((desired_dirname, available_langs))
         } else {
             Err(
                 LanguageNegotiatedSnafu {
@@ -102,196 +107,548 @@ fn resolve_desired_lang(lang_name: Option<String>, lang_dir: &PathBuf)
         }
    }
 
+// the concurrent bundle keeps its memoizer behind a lock of its own,
+// so the messages can be formatted from the parallel jobs.
 struct LanguageSystem {
-    pub bundle: fluent::FluentBundle<FluentResource>,
+    pub bundle: FluentBundle<FluentResource>,
     pub current_lang: LanguageIdentifier,
-    pub current_lang_dir_path: PathBuf,
+    // `None` for the built-in messages
+    pub current_lang_dir_path: Option<PathBuf>,
+    // the formatted messages are padded and bracketed, see `pseudo()`
+    pub pseudo: bool,
+    // how the language files were chosen, `None` for the built-in messages
+    pub lang_dir_origin: Option<LangDirOrigin>,
+    pub desired_lang: Option<String>,
+    pub score: Option<u8>,
 }
 
-unsafe impl Sync for LanguageSystem {}
-unsafe impl Send for LanguageSystem {}
+// replaced as a whole when the language is switched, the formatting holds
+// its own `Arc` so the lock is only taken to clone it.
+static LANG: RwLock<Option<Arc<LanguageSystem>>> = RwLock::new(None);
+
+static ENV_LANGUAGES_LOCATION: &str = "RUST_RECURSIVELY_ACTION_PATH";
+// the directory of the program under the data directories.
+static APP_DATA_DIR_NAME: &str = "rust_recursive_action";
+pub static DEFAULT_LANG_DIR: &str = "i18n/fluent";
+
+// the messages compiled in, used when the language files can't be loaded.
+static BUILTIN_LANG: &str = "en-US";
+static BUILTIN_FTL: &str = include_str!("../i18n/fluent/en-US/lang.ftl");
+static BUILTIN: LazyLock<Arc<LanguageSystem>> = LazyLock::new(|| Arc::new(LanguageSystem::builtin()));
+
+// the letters of English with an accent, the text still reads as English.
+fn pseudo_accent(c: char) -> char {
+    match c {
+        'a' => 'å', 'b' => 'ƀ', 'c' => 'ç', 'd' => 'ð', 'e' => 'é', 'f' => 'ƒ', 'g' => 'ĝ',
+        'h' => 'ĥ', 'i' => 'î', 'j' => 'ĵ', 'k' => 'ķ', 'l' => 'ļ', 'm' => 'ɱ', 'n' => 'ñ',
+        'o' => 'ö', 'p' => 'þ', 'q' => 'ǫ', 'r' => 'ŕ', 's' => 'š', 't' => 'ţ', 'u' => 'û',
+        'v' => 'ṽ', 'w' => 'ŵ', 'x' => 'ẋ', 'y' => 'ý', 'z' => 'ž',
+        'A' => 'Å', 'B' => 'Ɓ', 'C' => 'Ç', 'D' => 'Ð', 'E' => 'É', 'F' => 'Ƒ', 'G' => 'Ĝ',
+        'H' => 'Ĥ', 'I' => 'Î', 'J' => 'Ĵ', 'K' => 'Ķ', 'L' => 'Ļ', 'M' => 'Ṁ', 'N' => 'Ñ',
+        'O' => 'Ö', 'P' => 'Þ', 'Q' => 'Ǫ', 'R' => 'Ŕ', 'S' => 'Š', 'T' => 'Ţ', 'U' => 'Û',
+        'V' => 'Ṽ', 'W' => 'Ŵ', 'X' => 'Ẋ', 'Y' => 'Ý', 'Z' => 'Ž',
+        _ => c,
+    }
+}
 
-use std::sync::OnceLock;
+// applied to the text of the messages only, the variables are left as they're given.
+fn pseudo_transform(text: &str) -> Cow<'_, str> {
+    Cow::Owned(text.chars().map(pseudo_accent).collect())
+}
 
-static LANG: OnceLock<Mutex<Arc<LanguageSystem>>> = OnceLock::new();
+// the translations are often longer than English, about a third is added
+// so a message cut off by a fixed width shows up. The brackets show where
+// a message starts and ends, the text out of them isn't translated.
+fn pseudo_wrap(message: String) -> String {
+    let width = message.lines().map(|a| a.chars().count()).max().unwrap_or_default();
+    let padding = "~".repeat(width.div_ceil(3));
+    let lines = message.lines()
+        .map(|a| format!("[{a} {padding}]"))
+        .collect::<Vec<_>>();
+    lines.join("\n")
+}
 
-static ENV_LANGUAGES_LOCATION: &'static str = "RUST_RECURSIVELY_ACTION_PATH";
-#[cfg(target_os = "linux")]
-static ENV_APP_INSTALLATION_LOC: &'static str = ".local/share/rust_recursive_action";
+/// Where a place to look for the language directory comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LangDirOrigin {
+    CurrentDir,
+    EnvPath,
+    XdgDataHome,
+    // `$XDG_DATA_HOME` unset, `~/.local/share`
+    XdgDataHomeDefault,
+    XdgDataDirs,
+    // `$XDG_DATA_DIRS` unset, `/usr/local/share` and `/usr/share`
+    XdgDataDirsDefault,
+    // `../share` of the executable's directory, for the installation prefixes
+    Executable,
+}
 
-fn check_lang_dir(dir_str: &str) -> PathBuf {
-    let lang_dir_splitted = dir_str.split(std::path::MAIN_SEPARATOR_STR);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LangDirStatus {
+    Found,
+    // the variable or the directory it's relative to isn't there
+    NotSet,
+    // the XDG spec says the relative paths are ignored
+    Relative,
+    Missing,
+    NotADirectory,
+}
 
-    // If in current dir
-    {
-        let mut tmp = env::current_dir().expect("Get current dir failed.");
-        tmp.extend(lang_dir_splitted.clone());
-        if tmp.exists() {
-            return tmp;
-        }
+/// A place tried for the language directory, `path` is `None` when there's nothing to try.
+#[derive(Debug, Clone)]
+pub struct LangDirCandidate {
+    pub origin: LangDirOrigin,
+    pub path: Option<PathBuf>,
+    pub status: LangDirStatus,
+}
+
+impl LangDirCandidate {
+    fn not_set(origin: LangDirOrigin) -> Self {
+        Self { origin, path: None, status: LangDirStatus::NotSet }
     }
 
-    // If setted in environment variable
-    {
-        if let Ok(rust_recursively_action_path) = env::var(ENV_LANGUAGES_LOCATION) {
-            use std::str::FromStr;
-            #[allow(irrefutable_let_patterns)]
-            if let Ok(mut lang_dir) = PathBuf::from_str(&rust_recursively_action_path) {
-                lang_dir.extend(lang_dir_splitted.clone());
-                if lang_dir.exists() {
-                    return lang_dir;
-                }
-            }
-        }
+    fn relative(origin: LangDirOrigin, path: PathBuf) -> Self {
+        Self { origin, path: Some(path), status: LangDirStatus::Relative }
     }
 
-    // If `i18n/fluent` is at home directoru
-    {
-        if let Some(mut hd) = env::home_dir() {
-            hd.extend(ENV_APP_INSTALLATION_LOC.split(std::path::MAIN_SEPARATOR));
-            hd.extend(lang_dir_splitted.clone());
-            if hd.exists() {
-                return hd;
-            }
+    // `base/<dir_str>` looked at.
+    fn under(origin: LangDirOrigin, base: &Path, dir_str: &str) -> Self {
+        let mut path = base.to_path_buf();
+        path.extend(dir_str.split(std::path::MAIN_SEPARATOR_STR));
+        let status = if !path.exists() {
+            LangDirStatus::Missing
+        } else if !path.is_dir() {
+            LangDirStatus::NotADirectory
+        } else {
+            LangDirStatus::Found
+        };
+        Self { origin, path: Some(path), status }
+    }
+}
+
+// a non-empty variable.
+fn env_var_set(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|a| !a.is_empty())
+}
+
+/// Every place looked for the language directory, in the order they're tried:
+/// the current directory, `$RUST_RECURSIVELY_ACTION_PATH`, `$XDG_DATA_HOME`,
+/// `$XDG_DATA_DIRS` and the `share` directory next to the executable's one.
+pub fn lang_dir_candidates(dir_str: &str) -> Vec<LangDirCandidate> {
+    let mut candidates = vec![];
+
+    match env::current_dir() {
+        Ok(cwd) => candidates.push(LangDirCandidate::under(LangDirOrigin::CurrentDir, &cwd, dir_str)),
+        Err(_) => candidates.push(LangDirCandidate::not_set(LangDirOrigin::CurrentDir)),
+    }
+
+    match env_var_set(ENV_LANGUAGES_LOCATION) {
+        Some(p) => candidates.push(LangDirCandidate::under(LangDirOrigin::EnvPath, Path::new(&p), dir_str)),
+        None => candidates.push(LangDirCandidate::not_set(LangDirOrigin::EnvPath)),
+    }
+
+    let app_dir = PathBuf::from(APP_DATA_DIR_NAME);
+    // shown as ignored, the default is looked at instead.
+    if let Some(p) = env_var_set("XDG_DATA_HOME").map(PathBuf::from).filter(|a| a.is_relative()) {
+        candidates.push(LangDirCandidate::relative(LangDirOrigin::XdgDataHome, p));
+    }
+    match xdg_data_home() {
+        Some((p, origin)) => candidates.push(LangDirCandidate::under(origin, &p.join(&app_dir), dir_str)),
+        None => candidates.push(LangDirCandidate::not_set(LangDirOrigin::XdgDataHomeDefault)),
+    }
+
+    let (data_dirs, origin) = match env_var_set("XDG_DATA_DIRS") {
+        Some(v) => (env::split_paths(&v).collect::<Vec<_>>(), LangDirOrigin::XdgDataDirs),
+        None => (vec![PathBuf::from("/usr/local/share"), PathBuf::from("/usr/share")],
+            LangDirOrigin::XdgDataDirsDefault),
+    };
+    for p in data_dirs.into_iter().filter(|a| !a.as_os_str().is_empty()) {
+        if p.is_relative() {
+            candidates.push(LangDirCandidate::relative(origin, p));
+        } else {
+            candidates.push(LangDirCandidate::under(origin, &p.join(&app_dir), dir_str));
         }
     }
 
-    unimplemented!("Can't find the language installation directory. Since there's no a installation process\nPut the `i18n` directory into `.local/share/rust_recursive_action`\nor set the env variable `RUST_RECURSIVELY_ACTION_PATH` to where i18n located.");
+    // `<prefix>/bin/rust_recursively_action` with `<prefix>/share/...`
+    let prefix = env::current_exe()
+        .and_then(fs::canonicalize)
+        .ok()
+        .and_then(|a| a.parent().and_then(|b| b.parent()).map(|b| b.to_path_buf()));
+    match prefix {
+        Some(p) => {
+            let p = p.join("share").join(&app_dir);
+            candidates.push(LangDirCandidate::under(LangDirOrigin::Executable, &p, dir_str));
+        },
+        None => candidates.push(LangDirCandidate::not_set(LangDirOrigin::Executable)),
+    }
+
+    candidates
+}
+
+// the XDG spec takes a relative `$XDG_DATA_HOME` as unset, `~/.local/share` is used then.
+fn xdg_data_home() -> Option<(PathBuf, LangDirOrigin)> {
+    match env_var_set("XDG_DATA_HOME").map(PathBuf::from) {
+        Some(p) if p.is_absolute() => Some((p, LangDirOrigin::XdgDataHome)),
+        _ => env::home_dir().map(|hd| (hd.join(".local").join("share"), LangDirOrigin::XdgDataHomeDefault)),
+    }
+}
+
+/// `$XDG_DATA_HOME`, or `~/.local/share` when it's unset or relative,
+/// the first of the data directories the language files are looked for in.
+pub fn user_data_dir() -> Option<PathBuf> {
+    xdg_data_home().map(|(p, _)| p)
+}
+
+/// The directory of the program under a data directory like `/usr/share`.
+pub fn app_data_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(APP_DATA_DIR_NAME)
+}
+
+/// Where the language files are installed under a data directory.
+pub fn lang_install_dir(data_dir: &Path) -> PathBuf {
+    let mut dir = app_data_dir(data_dir);
+    dir.extend(DEFAULT_LANG_DIR.split('/'));
+    dir
+}
+
+fn check_lang_dir(dir_str: &str) -> Result<(PathBuf, LangDirOrigin)> {
+    lang_dir_candidates(dir_str)
+        .into_iter()
+        .find(|a| a.status == LangDirStatus::Found)
+        .and_then(|a| a.path.map(|p| (p, a.origin)))
+        .context(NotFoundLanguageFilesSnafu {
+            file_location: dir_str.to_owned(),
+        })
+}
+
+pub fn describe_origin(origin: LangDirOrigin) -> String {
+    match origin {
+        LangDirOrigin::CurrentDir => msg::lang_search_origin_cwd(),
+        LangDirOrigin::EnvPath => msg::lang_search_origin_env(ENV_LANGUAGES_LOCATION),
+        LangDirOrigin::XdgDataHome => msg::lang_search_origin_env("XDG_DATA_HOME"),
+        LangDirOrigin::XdgDataHomeDefault => msg::lang_search_origin_default_or_relative("XDG_DATA_HOME"),
+        LangDirOrigin::XdgDataDirs => msg::lang_search_origin_env("XDG_DATA_DIRS"),
+        LangDirOrigin::XdgDataDirsDefault => msg::lang_search_origin_default("XDG_DATA_DIRS"),
+        LangDirOrigin::Executable => msg::lang_search_origin_exe(),
+    }
+}
+
+/// `--print-lang-search-path`, every place tried and why it isn't used.
+pub fn print_lang_search_path(lang_dir: Option<String>) {
+    let lang_dir = lang_dir.unwrap_or(DEFAULT_LANG_DIR.to_owned());
+    let mut used = false;
+    for (i, c) in lang_dir_candidates(&lang_dir).iter().enumerate() {
+        let status = match c.status {
+            LangDirStatus::Found if !used => {
+                used = true;
+                msg::lang_search_used()
+            },
+            LangDirStatus::Found => msg::lang_search_shadowed(),
+            LangDirStatus::NotSet => msg::lang_search_not_set(),
+            LangDirStatus::Relative => msg::lang_search_relative(),
+            LangDirStatus::Missing => msg::lang_search_missing(),
+            LangDirStatus::NotADirectory => msg::lang_search_not_a_directory(),
+        };
+        let path = c.path.as_ref()
+            .map(|a| a.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "-".to_owned());
+        println!("{}", msg::lang_search_entry(i + 1, path, describe_origin(c.origin), status));
+    }
+    if !used {
+        println!("{}", msg::lang_search_builtin());
+    }
+}
+
+fn fluent_errors<E: std::fmt::Display>(errors: &[E]) -> Vec<String> {
+    errors.iter()
+        .map(|a| a.to_string())
+        .collect()
 }
 
 impl LanguageSystem {
-    pub fn new(desired_lang: Option<String>, lang_dir: Option<String>) -> Self {
-        let lang_dir = lang_dir.unwrap_or("i18n/fluent".to_string());
-        let lang_dir = check_lang_dir(&lang_dir);
+    pub fn new(desired_lang: Option<String>, lang_dir: Option<String>) -> Result<Self> {
+        if desired_lang.as_deref()
+            .and_then(|a| a.parse::<LanguageIdentifier>().ok())
+            .is_some_and(|a| a == langid!("en-XA")) {
+            return Ok(Self::pseudo());
+        }
+        let lang_dir = lang_dir.unwrap_or(DEFAULT_LANG_DIR.to_owned());
+        let (lang_dir, lang_dir_origin) = check_lang_dir(&lang_dir)?;
 
-        let ordered_langs = resolve_desired_lang(desired_lang.clone(), &lang_dir)
-            .expect(&format!("fetch languages {:?} failed.", desired_lang));
+        let (desired_dirname, ordered_langs) = resolve_desired_lang(desired_lang.clone(), &lang_dir)?;
         let v = ordered_langs
             .iter()
             .map(|a| { a.lid.clone() })
             .collect();
-        let mut bundle = FluentBundle::new(v);
-        let desired_lang_helper_s = &ordered_langs.first().unwrap();
+        let mut bundle = FluentBundle::new_concurrent(v);
+        fluent_functions::add_functions(&mut bundle);
+        let desired_lang_helper_s = ordered_langs.first()
+            .context(LanguageNegotiatedSnafu {
+                desired_dirname: desired_lang.unwrap_or_default(),
+                available_langs: Vec::<String>::new(),
+            })?;
 
         { // add ftl files under desired directory to bundle.
             let read_dir = fs::read_dir(&desired_lang_helper_s.dir_path)
-                .expect(&format!("read language dir {:?} failed", &desired_lang_helper_s.dir_path));
-
-            for entry in read_dir {
-                if let Ok(dir_entry) = entry {
-                    let path = dir_entry.path();
-                    let path_extension = path.extension()
-                        .expect("File extension is not correct")
-                        .to_string_lossy();
-                    if path.is_file() && path.extension().is_some()
-                        && path_extension == "ftl" {
-                            {
-                                let mut f = fs::File::open(path)
-                                    .expect("failed to open one of ftl files.");
-                                let mut s = String::new();
-                                f.read_to_string(&mut s).expect("read ftl file to string failed.");
-                                let r = FluentResource::try_new(s)
-                                    .expect("Could not parse an FTL string.");
-                                bundle.add_resource(r)
-                                    .expect("Failed to add FTL resources to the bundle.");
-                                }
-                    }
+                .context(LanguageIOSnafu {
+                    path: desired_lang_helper_s.dir_path.to_string_lossy().into_owned()
+                })?;
+
+            for dir_entry in read_dir.flatten() {
+                let path = dir_entry.path();
+                // the other files, like a `README`, are left alone.
+                if !path.is_file() || path.extension().is_none_or(|a| a != "ftl") {
+                    continue;
                 }
+                let file_path = path.to_string_lossy().into_owned();
+                let s = fs::read_to_string(&path)
+                    .context(LanguageIOSnafu { path: file_path.clone() })?;
+                let r = FluentResource::try_new(s)
+                    .map_err(|(_, errors)| ParseFluentSnafu {
+                        file_path: file_path.clone(),
+                        errors: fluent_errors(&errors),
+                    }.build())?;
+                bundle.add_resource(r)
+                    .map_err(|errors| ParseFluentSnafu {
+                        file_path: file_path.clone(),
+                        errors: fluent_errors(&errors),
+                    }.build())?;
             }
         }
 
-        Self {
+        Ok(Self {
             bundle,
             current_lang: desired_lang_helper_s.lid.clone(),
-            current_lang_dir_path: desired_lang_helper_s.dir_path.clone(),
-        }
+            current_lang_dir_path: Some(desired_lang_helper_s.dir_path.clone()),
+            pseudo: false,
+            lang_dir_origin: Some(lang_dir_origin),
+            desired_lang: Some(desired_dirname),
+            score: Some(desired_lang_helper_s.score),
+        })
     }
-}
 
-pub fn build_language_0<'a>(msg_key: &str) -> String {
-    match LANG.get()
-        .expect("Uninitialized language bundle.").lock() {
-        Ok(bs) => {
-
-            let expect_errmsg = format!("failed to find message {}", msg_key);
-            let msg = bs.bundle
-                .get_message(msg_key)
-                .expect(&expect_errmsg);
-            let mut errors = vec![];
-            let pattern = msg.value()
-                .expect("Message has no value.");
-            let v = bs.bundle.format_pattern(pattern, None, &mut errors);
-            v.to_string()
-        },
-        Err(e) => {
-            panic!("Language bundle mutext poisoned. {e}");
+    /// The English messages compiled into the binary.
+    fn builtin() -> Self {
+        let current_lang = langid!("en-US");
+        let mut bundle = FluentBundle::new_concurrent(vec![current_lang.clone()]);
+        fluent_functions::add_functions(&mut bundle);
+        // the same file is checked whenever it's loaded from the disk,
+        // a broken entry would only be missing here.
+        let r = FluentResource::try_new(BUILTIN_FTL.to_owned())
+            .unwrap_or_else(|(r, _)| r);
+        bundle.add_resource_overriding(r);
+        Self {
+            bundle,
+            current_lang,
+            current_lang_dir_path: None,
+            pseudo: false,
+            lang_dir_origin: None,
+            desired_lang: None,
+            score: None,
         }
     }
+
+    /// `en-XA`, the built-in English messages with the letters accented,
+    /// padded and bracketed. The messages which aren't formatted through
+    /// the catalogue are left plain, as are the variables.
+    fn pseudo() -> Self {
+        let mut system = Self::builtin();
+        system.bundle.locales = vec![langid!("en-XA"), langid!("en-US")];
+        system.bundle.set_transform(Some(pseudo_transform));
+        system.current_lang = langid!("en-XA");
+        system.pseudo = true;
+        system
+    }
 }
 
-pub fn build_language_1<'a, T>(msg_key: &str, arg_name: &str, v: T) -> String
-    where T: Into<FluentValue<'a>> {
-    build_language(msg_key,
-        vec![(arg_name, v.into())])
+/// The language in use and how it was chosen, for `doctor`.
+#[derive(Debug, Clone)]
+pub struct LanguageDiagnosis {
+    pub lang: String,
+    pub pseudo: bool,
+    // the files of the language and where their directory was found
+    pub lang_dir: Option<(PathBuf, LangDirOrigin)>,
+    pub desired_lang: Option<String>,
+    // of `language_matches_score`, 15 for the same language, script and region
+    pub score: Option<u8>,
 }
 
-pub fn build_language_fns<'a, F>(msg_key: &str, args_pairs_builders: Vec<(&str, F)>) -> String
-where F: FnOnce() -> FluentValue<'a>{
-    let args_pairs: Vec<_> = args_pairs_builders.into_iter()
-        .map(
-            |a| {
-            (a.0,
-             a.1())
-            }
-            )
-        .collect();
-    build_language(msg_key, args_pairs)
+pub fn language_diagnosis() -> LanguageDiagnosis {
+    let system = current_lang_system();
+    LanguageDiagnosis {
+        lang: system.current_lang.to_string(),
+        pseudo: system.pseudo,
+        lang_dir: system.current_lang_dir_path.clone().zip(system.lang_dir_origin),
+        desired_lang: system.desired_lang.clone(),
+        score: system.score,
+    }
 }
 
+// before `init_lang`, the built-in messages are used.
+fn current_lang_system() -> Arc<LanguageSystem> {
+    LANG.read()
+        .unwrap_or_else(|a| a.into_inner())
+        .clone()
+        .unwrap_or_else(|| BUILTIN.clone())
+}
 
-pub fn build_language<'a>(msg_key: &str, args_pairs: Vec<(&str, FluentValue)>) -> String {
-    match LANG.get() {
-        Some(lang) => {
-            match lang.lock() {
-                Ok(bs) => {
-                    let expect_errmsg = format!("failed to find message {}", msg_key);
-                    let msg = bs
-                        .bundle
-                        .get_message(msg_key)
-                        .expect(&expect_errmsg);
+/// What is done with the problems of formatting a message, like a translation
+/// using a variable the message isn't given, or missing the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FormatErrorMode {
+    Ignore,
+    // printed on stderr, the default of the debug builds and of `--verbose`
+    Warn,
+    // a panic, so the tests fail
+    Strict,
+}
 
-                    let pattern = msg.value()
-                        .expect("Message has no value");
+static ENV_FORMAT_ERRORS: &str = "RUST_RECURSIVELY_ACTION_FORMAT_ERRORS";
+// 0 until decided, then `FormatErrorMode as u8 + 1`
+static FORMAT_ERROR_MODE: AtomicU8 = AtomicU8::new(0);
+
+/// `RUST_RECURSIVELY_ACTION_FORMAT_ERRORS=ignore|warn|strict`, otherwise
+/// the problems are printed by the debug builds only.
+pub fn format_error_mode() -> FormatErrorMode {
+    let mode = match FORMAT_ERROR_MODE.load(Ordering::Relaxed) {
+        1 => return FormatErrorMode::Ignore,
+        2 => return FormatErrorMode::Warn,
+        3 => return FormatErrorMode::Strict,
+        _ => {
+            match env::var(ENV_FORMAT_ERRORS).as_deref() {
+                Ok("ignore") => FormatErrorMode::Ignore,
+                Ok("warn") => FormatErrorMode::Warn,
+                Ok("strict") => FormatErrorMode::Strict,
+                _ if cfg!(debug_assertions) => FormatErrorMode::Warn,
+                _ => FormatErrorMode::Ignore,
+            }
+        }
+    };
+    set_format_error_mode(mode);
+    mode
+}
 
-                    let mut args  = FluentArgs::new();
-                    for kv in args_pairs {
-                        args.set(kv.0,
-                            kv.1);
-                    }
+pub fn set_format_error_mode(mode: FormatErrorMode) {
+    FORMAT_ERROR_MODE.store(mode as u8 + 1, Ordering::Relaxed);
+}
 
-                    let mut errors = vec![];
-                    let value = bs.bundle.format_pattern(pattern, Some(&args), &mut errors);
-                    value.to_string()
-                },
-                Err(e) => {
-                    panic!("Language bundle mutex poisoned {e:?}")
-                }
+// not localized, the report would format messages again.
+fn report_format_problems(msg_key: &str, lang: &LanguageIdentifier, problems: &[String]) {
+    match format_error_mode() {
+        FormatErrorMode::Ignore => {},
+        FormatErrorMode::Warn => {
+            for p in problems {
+                eprintln!("warning: message `{}` of `{}`: {}", msg_key, lang, p);
             }
         },
-        None => {
-            panic!("Uninitialized lang bundle")
+        FormatErrorMode::Strict => {
+            panic!("message `{}` of `{}`: {}", msg_key, lang, problems.join("; "));
         }
     }
 }
 
-pub fn init_lang(desired_lang: Option<String>, lang_dir: Option<String>) {
-    if LANG
-        .set(Mutex::new(
-                Arc::new(LanguageSystem::new(desired_lang, lang_dir))))
-            .is_err()  {
-                panic!("set initialized Language system failed.");
+// `None` when the bundle has no such message.
+fn format_message(bs: &LanguageSystem, msg_key: &str, args: &FluentArgs) -> Option<String> {
+    let pattern = bs.bundle
+        .get_message(msg_key)?
+        .value()?;
+    let mut errors = vec![];
+    let value = bs.bundle.format_pattern(pattern, Some(args), &mut errors);
+    if !errors.is_empty() {
+        let problems = errors.iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>();
+        report_format_problems(msg_key, &bs.current_lang, &problems);
     }
+    if bs.pseudo {
+        return Some(pseudo_wrap(value.into_owned()));
+    }
+    Some(value.into_owned())
 }
 
+/// Formats the message of the current language, a message missing in the
+/// translation falls back to the built-in English one.
+/// The typed functions of `messages` are the ones to call.
+pub fn build_language(msg_key: &str, args_pairs: Vec<(&str, FluentValue)>) -> String {
+    let mut args  = FluentArgs::new();
+    for kv in args_pairs {
+        args.set(kv.0,
+            kv.1);
+    }
 
+    let bs = current_lang_system();
+    if let Some(v) = format_message(&bs, msg_key, &args) {
+        return v;
+    }
+    report_format_problems(msg_key, &bs.current_lang, &["no such message".to_owned()]);
+    format_message(&BUILTIN, msg_key, &args)
+        .unwrap_or_else(|| msg_key.to_owned())
+}
+
+// the messages of the catalogue the language lacks, or can't format with their variables,
+// like a translation using a variable the message isn't given.
+fn catalogue_problems(system: &LanguageSystem) -> Vec<String> {
+    let mut problems = vec![];
+    for (key, vars) in msg::CATALOGUE {
+        let Some(pattern) = system.bundle.get_message(key).and_then(|a| a.value()) else {
+            problems.push(format!("`{}`: no such message", key));
+            continue;
+        };
+        // a number suits the plural selectors and `BYTES` as well as the text.
+        let mut args = FluentArgs::new();
+        for v in *vars {
+            args.set(*v, 1);
+        }
+        let mut errors = vec![];
+        system.bundle.format_pattern(pattern, Some(&args), &mut errors);
+        problems.extend(errors.iter().map(|a| format!("`{}`: {}", key, a)));
+    }
+    problems
+}
 
+/// Loads the language and switches to it, the messages built afterwards use it
+/// while the ones being formatted finish with the previous one.
+/// The current language is kept when the new one can't be loaded.
+pub fn switch_lang(desired_lang: Option<String>, lang_dir: Option<String>) -> Result<()> {
+    let system = LanguageSystem::new(desired_lang, lang_dir)?;
+    if format_error_mode() == FormatErrorMode::Strict {
+        let problems = catalogue_problems(&system);
+        if !problems.is_empty() {
+            report_format_problems("catalogue", &system.current_lang, &problems);
+        }
+    }
+    *LANG.write().unwrap_or_else(|a| a.into_inner()) = Some(Arc::new(system));
+    Ok(())
+}
+
+/// Loads the language files, falling back to the built-in English messages
+/// when they can't be loaded, the problem is reported once on stderr.
+pub fn init_lang(desired_lang: Option<String>, lang_dir: Option<String>) {
+    if let Err(e) = switch_lang(desired_lang, lang_dir) {
+        *LANG.write().unwrap_or_else(|a| a.into_inner()) = Some(BUILTIN.clone());
+        // after setting, the report itself is made of the messages.
+        e.report_warning();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_languages_format_the_catalogue() {
+        // a missing message or an unknown variable panics in `switch_lang`.
+        set_format_error_mode(FormatErrorMode::Strict);
+        // the tests run in the package directory, the default one is found under it.
+        let lang_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_LANG_DIR);
+        for lang in ["en-US", "zh_CN", "zh_TW"] {
+            switch_lang(Some(lang.to_owned()), None).unwrap();
+            let system = current_lang_system();
+            assert_eq!(system.current_lang_dir_path.as_deref(), Some(lang_dir.join(lang).as_path()));
+            for (key, vars) in msg::CATALOGUE {
+                build_language(key, vars.iter().map(|a| (*a, FluentValue::from(1))).collect());
+            }
+        }
+    }
+}
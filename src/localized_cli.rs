@@ -0,0 +1,180 @@
+use clap::error::{ContextKind, ErrorKind};
+use clap::{Arg, ArgAction, Command, CommandFactory, FromArgMatches};
+
+use crate::messages as msg;
+
+// the one of clap without `{usage-heading}`, which is fixed to English.
+fn help_template() -> String {
+    format!("{{before-help}}{{about-with-newline}}\n{} {{usage}}\n\n{{all-args}}{{after-help}}",
+        msg::help_usage_heading())
+}
+
+// clap writes the flags it adds, the headings and the sections itself,
+// they're replaced by the ones of the catalogue in every subcommand.
+fn localize_subcommands(cmd: Command) -> Command {
+    cmd.disable_help_flag(true)
+        .arg(Arg::new("help")
+            .short('h')
+            .long("help")
+            .action(ArgAction::Help)
+            .help(msg::help_helper()))
+        .subcommand_help_heading(msg::help_commands_heading())
+        .subcommand_value_name(msg::help_command_value_name())
+        .help_template(help_template())
+        .mut_args(|a| {
+            let a = localize_values(a);
+            if a.get_help_heading().is_some() {
+                return a;
+            }
+            let heading = if a.is_positional() {
+                msg::help_arguments_heading()
+            } else {
+                msg::help_options_heading()
+            };
+            a.help_heading(heading)
+        })
+        .mut_subcommands(localize_subcommands)
+}
+
+// the possible and the default values are listed by clap under English labels,
+// they're written into the long help instead.
+fn localize_values(a: Arg) -> Arg {
+    // the flags have the values `true` and `false` too.
+    if !a.get_action().takes_values() {
+        return a;
+    }
+    let values = a.get_possible_values()
+        .into_iter()
+        .filter(|v| !v.is_hide_set())
+        .collect::<Vec<_>>();
+    let defaults = a.get_default_values()
+        .iter()
+        .map(|v| v.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if (values.is_empty() || a.is_hide_possible_values_set())
+        && (defaults.is_empty() || a.is_hide_default_value_set()) {
+        return a;
+    }
+
+    let mut help = a.get_long_help()
+        .or(a.get_help())
+        .map(|h| h.to_string())
+        .unwrap_or_default();
+    if !values.is_empty() && !a.is_hide_possible_values_set() {
+        help.push_str(&format!("\n\n{}:", msg::help_possible_values_heading()));
+        for v in &values {
+            match v.get_help() {
+                Some(h) => help.push_str(&format!("\n- {}: {}", v.get_name(), h)),
+                None => help.push_str(&format!("\n- {}", v.get_name())),
+            }
+        }
+    }
+    if !defaults.is_empty() && !a.is_hide_default_value_set() {
+        help.push_str(&format!("\n\n[{}]", msg::help_default_value(defaults.join(", "))));
+    }
+    a.long_help(help)
+        .hide_possible_values(true)
+        .hide_default_value(true)
+}
+
+/// The command of `T` with all of the help built from the messages.
+pub fn localized_command<T: CommandFactory>() -> Command {
+    let cmd = T::command()
+        .disable_version_flag(true)
+        .arg(Arg::new("version")
+            .short('V')
+            .long("version")
+            .action(ArgAction::Version)
+            .help(msg::version_helper()));
+    let mut cmd = localize_subcommands(cmd);
+    // the `help` subcommand only exists once built.
+    cmd.build();
+    cmd.mut_subcommand("help", |a| {
+        a.about(msg::help_subcommand_about())
+            .help_template(help_template())
+    })
+}
+
+fn context(e: &clap::Error, kind: ContextKind) -> String {
+    e.get(kind)
+        .map(|a| a.to_string())
+        .unwrap_or_default()
+}
+
+/// The usage errors clap can tell apart, the others are left in English.
+fn localize_error(e: &clap::Error) -> Option<String> {
+    let arg = context(e, ContextKind::InvalidArg);
+    let value = context(e, ContextKind::InvalidValue);
+    let error = match e.kind() {
+        ErrorKind::UnknownArgument => msg::clap_unknown_argument(arg.clone()),
+        ErrorKind::InvalidValue if value.is_empty() => msg::clap_value_required(arg.clone()),
+        ErrorKind::InvalidValue => msg::clap_invalid_value(value, arg.clone()),
+        ErrorKind::ValueValidation => {
+            let reason = std::error::Error::source(e)
+                .map(|a| a.to_string())
+                .unwrap_or_default();
+            msg::clap_value_validation(value, arg.clone(), reason)
+        },
+        ErrorKind::InvalidSubcommand => {
+            msg::clap_invalid_subcommand(context(e, ContextKind::InvalidSubcommand))
+        },
+        ErrorKind::ArgumentConflict => {
+            msg::clap_argument_conflict(arg.clone(), context(e, ContextKind::PriorArg))
+        },
+        ErrorKind::MissingRequiredArgument => msg::clap_missing_required(arg.clone()),
+        _ => return None,
+    };
+
+    let mut out = format!("error: {error}\n");
+    let mut tips = vec![];
+    if e.get(ContextKind::ValidValue).is_some() {
+        tips.push(msg::clap_possible_values(context(e, ContextKind::ValidValue)));
+    }
+    if e.get(ContextKind::SuggestedArg).is_some() {
+        tips.push(msg::clap_similar_argument(context(e, ContextKind::SuggestedArg)));
+    }
+    if e.get(ContextKind::SuggestedValue).is_some() {
+        tips.push(msg::clap_similar_value(context(e, ContextKind::SuggestedValue)));
+    }
+    if e.get(ContextKind::SuggestedSubcommand).is_some() {
+        tips.push(msg::clap_similar_subcommand(context(e, ContextKind::SuggestedSubcommand)));
+    }
+    // the only free text tip clap gives to an unknown argument.
+    if e.kind() == ErrorKind::UnknownArgument && e.get(ContextKind::Suggested).is_some() {
+        tips.push(msg::clap_pass_as_value(arg));
+    }
+    if !tips.is_empty() {
+        out.push('\n');
+        let label = msg::error_hint();
+        for t in tips {
+            out.push_str(&format!("  {label}: {t}\n"));
+        }
+    }
+    if let Some(usage) = e.get(ContextKind::Usage) {
+        let usage = usage.to_string();
+        let usage = usage.strip_prefix("Usage: ").unwrap_or(&usage);
+        out.push_str(&format!("\n{} {}\n", msg::help_usage_heading(), usage));
+    }
+    out.push_str(&format!("\n{}", msg::clap_more_information()));
+    Some(out)
+}
+
+fn exit_with_clap_error(e: clap::Error) -> ! {
+    match localize_error(&e) {
+        Some(message) => {
+            eprintln!("{message}");
+            std::process::exit(e.exit_code())
+        },
+        // the help and the version are printed by clap.
+        None => e.exit(),
+    }
+}
+
+/// Like `Parser::parse`, with the localized help and usage errors.
+pub fn parse<T: CommandFactory + FromArgMatches>() -> T {
+    let matches = localized_command::<T>()
+        .try_get_matches()
+        .unwrap_or_else(|e| exit_with_clap_error(e));
+    T::from_arg_matches(&matches)
+        .unwrap_or_else(|e| exit_with_clap_error(e))
+}
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use snafu::prelude::*;
+
+use crate::GeneratingType;
+use crate::errors::*;
+use crate::git;
+use crate::invocation::{self, CargoInvocation};
+use crate::messages as msg;
+use crate::manifest::{self, LockChange, LockedPackage};
+
+// committing in one repository from several jobs would race on `.git/index.lock`.
+static COMMIT_LOCK: Mutex<()> = Mutex::new(());
+
+// the lines of `commit_message` out of `git diff --cached -- Cargo.lock`, for the bash
+// commands. Like `diff_locked_packages`, a single version replaced by another is an update.
+static LOCK_DIFF_AWK: &str = concat!(
+    r#"/^[ +-]name = "/ { split($0, a, "\""); k = a[2]; if (!(k in seen)) { seen[k] = 1; names[n++] = k } } "#,
+    r#"/^-version = "/ { split($0, a, "\""); olds[k] = olds[k] " " a[2]; o[k]++ } "#,
+    r#"/^\+version = "/ { split($0, a, "\""); news[k] = news[k] " " a[2]; w[k]++ } "#,
+    r#"END { for (i = 0; i < n; i++) { k = names[i]; "#,
+    r#"if (o[k] == 1 && w[k] == 1) { print "- " k olds[k] " ->" news[k]; continue } "#,
+    r#"c = split(news[k], v, " "); for (j = 1; j <= c; j++) print "- " k " " v[j] " (added)"; "#,
+    r#"c = split(olds[k], v, " "); for (j = 1; j <= c; j++) print "- " k " " v[j] " (removed)" } }"#,
+);
+
+/// The repositories of the projects which have no uncommitted change before the run,
+/// the repositories out of this set are never touched.
+pub fn clean_repos(projects: &[PathBuf]) -> Result<HashSet<PathBuf>> {
+    let mut checked = HashSet::new();
+    let mut clean = HashSet::new();
+    for p in projects {
+        let Some(repo) = git::find_enclosing_repo(p) else { continue; };
+        if !checked.insert(repo.clone()) {
+            continue;
+        }
+        if !git::is_dirty(&repo)? {
+            clean.insert(repo);
+        }
+    }
+    Ok(clean)
+}
+
+fn commit_subject(project: &Path) -> String {
+    let name = manifest::read_manifest(project)
+        .ok()
+        .and_then(|a| {
+            a.get("package")
+                .and_then(|p| p.get("name"))
+                .and_then(|n| n.as_str())
+                .map(|n| n.to_owned())
+        })
+        .or_else(|| project.file_name().map(|a| a.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+    format!("Update Cargo.lock of {name}")
+}
+
+pub fn commit_message(project: &Path, changes: &[LockChange]) -> String {
+    let mut msg = format!("{}\n\n", commit_subject(project));
+    for c in changes {
+        msg.push_str(&format!("- {c}\n"));
+    }
+    msg
+}
+
+/// The state of a project's `Cargo.lock` taken before `cargo update`.
+pub struct LockCommit {
+    project: PathBuf,
+    repo: PathBuf,
+    // `None` when the update creates the lock file
+    before: Option<Vec<LockedPackage>>,
+}
+
+impl LockCommit {
+    /// `None` when the project isn't in a clean repository or its lock file is ignored.
+    pub fn prepare(project: &Path, clean_repos: &HashSet<PathBuf>) -> Result<Option<Self>> {
+        let Some(repo) = git::find_enclosing_repo(project) else {
+            return Ok(None);
+        };
+        if !clean_repos.contains(&repo) {
+            eprintln!("{}", msg::git_commit_skip_dirty(repo.to_string_lossy().into_owned()));
+            return Ok(None);
+        }
+        if git::git_output(project, &["check-ignore", "-q", "--", manifest::LOCK_FILE_NAME]).is_ok() {
+            return Ok(None);
+        }
+        let before = manifest::read_lockfile(project)?;
+        Ok(Some(Self {
+            project: project.to_path_buf(),
+            repo,
+            before,
+        }))
+    }
+
+    fn dry_run_changes(&self, update: &CargoInvocation) -> Result<Vec<LockChange>> {
+        let mut dry_run = update.clone();
+        dry_run.args.push("--dry-run".to_owned());
+        let output = dry_run.command()
+            .output()
+            .context(StartCargoSnafu {
+                dynamic_errmsg: msg::start_cargo_subcommand_failed(dry_run.command_line()),
+            })?;
+        if !output.status.success() {
+            return Err(Error::ProcessExit {
+                code: output.status.code(),
+                stdout: output.stdout,
+                stderr: output.stderr,
+            });
+        }
+        Ok(manifest::parse_cargo_update_output(&String::from_utf8_lossy(&output.stderr)))
+    }
+
+    /// Commits the changed `Cargo.lock` after a successful update,
+    /// or shows what would be committed for the other generating types.
+    pub fn finish(self, update: &CargoInvocation, ge_ty: GeneratingType) -> Result<()> {
+        let project_str = self.project.to_string_lossy().into_owned();
+        match ge_ty {
+            GeneratingType::Makefile | GeneratingType::Ninja => {
+                Ok(())
+            },
+            GeneratingType::BashCommands => {
+                // the changes are unknown before the commands run, the message is made
+                // from the staged diff. A new lock file is staged and committed too.
+                println!("(cd {dir} && git add -- {lock} && {{ git diff --cached --quiet -- {lock} || \
+{{ printf '%s\\n\\n' {subject}; git diff --cached -- {lock} | awk {awk}; }} | git commit -q -F - -- {lock}; }})",
+                    dir = invocation::shell_quote(&project_str),
+                    lock = manifest::LOCK_FILE_NAME,
+                    subject = invocation::shell_quote(&commit_subject(&self.project)),
+                    awk = invocation::shell_quote(LOCK_DIFF_AWK));
+                Ok(())
+            },
+            GeneratingType::DryRunDebug => {
+                // `cargo update --dry-run` still writes a missing lock file.
+                if self.before.is_none() {
+                    eprintln!("{}", msg::git_commit_new_lock(project_str.clone()));
+                    eprintln!("RUN: git add -- {} at {}", manifest::LOCK_FILE_NAME, project_str);
+                    eprintln!("RUN: git commit -- {} at {}", manifest::LOCK_FILE_NAME, project_str);
+                    return Ok(());
+                }
+                let changes = self.dry_run_changes(update)?;
+                if changes.is_empty() {
+                    eprintln!("{}", msg::git_commit_nothing(project_str));
+                    return Ok(());
+                }
+                eprintln!("RUN: git add -- {} at {}", manifest::LOCK_FILE_NAME, project_str);
+                eprintln!("RUN: git commit -- {} at {}", manifest::LOCK_FILE_NAME, project_str);
+                for line in commit_message(&self.project, &changes).lines() {
+                    eprintln!("    {line}");
+                }
+                Ok(())
+            },
+            GeneratingType::RunAsSubprocess => {
+                let after = manifest::read_lockfile(&self.project)?.unwrap_or_default();
+                let changes = manifest::diff_locked_packages(self.before.as_deref().unwrap_or_default(), &after);
+                if changes.is_empty() {
+                    return Ok(());
+                }
+                let message = commit_message(&self.project, &changes);
+
+                let _guard = COMMIT_LOCK.lock().unwrap_or_else(|a| a.into_inner());
+                git::git_output(&self.project, &["add", "--", manifest::LOCK_FILE_NAME])?;
+                git::git_output(&self.project, &["commit", "-q", "-m", &message, "--", manifest::LOCK_FILE_NAME])?;
+                eprintln!("{}", msg::git_commit_done(project_str, self.repo.to_string_lossy().into_owned()));
+                Ok(())
+            }
+        }
+    }
+}
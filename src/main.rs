@@ -1,19 +1,43 @@
+mod audit;
+mod build_files;
+mod clean;
+mod dep_graph;
+mod disk_usage;
+mod doctor;
 mod errors;
+mod fluent_functions;
+mod git;
+mod install_data;
+mod inventory;
+mod invocation;
 mod language_helpers;
-
+mod localized_cli;
+mod lock_commit;
+mod manifest;
+mod messages;
+mod project_list;
+mod report;
+mod scheduler;
+mod selection;
+mod shell_integration;
+mod toolchain;
+mod validation;
+
+use std::env;
+use std::ffi::OsString;
 use std::fs;
 use std::path::PathBuf;
-use std::{env, };
+use std::time::Instant;
 
-use std::process::Command;
-use clap::{Parser, ValueEnum, builder::PossibleValue};
-use fluent::FluentValue;
+use clap::{Parser, Subcommand, ValueEnum, builder::PossibleValue};
 use snafu::prelude::*;
 
 use strum::IntoStaticStr;
 
 use language_helpers as lh;
+use messages as msg;
 use errors::*;
+use invocation::CargoInvocation;
 
 fn get_cargo_directories(path_str: &str) -> Result<Vec<PathBuf>> {
     let mut dir_pathes = Vec::<PathBuf>::new();
@@ -22,16 +46,9 @@ fn get_cargo_directories(path_str: &str) -> Result<Vec<PathBuf>> {
 
     let mut marked_pathes = Vec::<PathBuf>::new();
 
-    let path = fs::canonicalize(&path_str)
+    let path = fs::canonicalize(path_str)
         .context(CanonilizingSnafu {
-            dynamic_errmsg:
-                lh::build_language_fns(
-                    "file-path-canonicalized-failed",
-                    vec![(
-                        "path_dir", || {
-                            fluent::FluentValue::from(path_str)
-                        }
-                    )])
+            dynamic_errmsg: msg::file_path_canonicalized_failed(path_str)
         })?;
 
     dir_pathes.push(path);
@@ -47,21 +64,18 @@ fn get_cargo_directories(path_str: &str) -> Result<Vec<PathBuf>> {
                 dynamic_errmsg:
                     "The algorithm has logical bugs if unwrap fails."
                     .to_string()
-            })?;
+            })?
+            .clone();
 
         let sub_items = {
-            let dir_iter = fs::read_dir(dir_path)
+            let dir_iter = fs::read_dir(&dir_path)
             .context(ReadDirSnafu {
-                dynamic_errmsg: lh::build_language_1(
-                                    "read-directory-failed",
-                                    "dir_path",
-                                    dir_path.to_str()
-                                )})?;
+                dynamic_errmsg: msg::read_directory_failed(dir_path.to_string_lossy().into_owned())})?;
             let mut ps = vec![];
             for dirent in dir_iter {
                 let p = dirent.context(
                     DirEntrySnafu {
-                        dynamic_errmsg: lh::build_language_0("read-directory-entry-failed")
+                        dynamic_errmsg: msg::read_directory_entry_failed()
                     })?
                     .path();
                 ps.push(p);
@@ -69,54 +83,43 @@ fn get_cargo_directories(path_str: &str) -> Result<Vec<PathBuf>> {
             ps
         };
 
-        if sub_items.iter().any(|a| {
-            "Cargo.toml" == a.as_path().file_name()
-                .expect(&lh::build_language_0("get-file-name-failed"))
-                .to_str()
-                .expect(
-                    &lh::build_language_0("osstring-to-string-failed"))
-        }) {
-           marked_pathes.push(dir_pathes.last().unwrap().clone());
+        if sub_items.iter().any(|a| a.file_name().is_some_and(|n| n == manifest::MANIFEST_FILE_NAME)) {
+           marked_pathes.push(dir_path.clone());
            marked_cargo_dir = true;
         }
 
-        let sub_items = sub_items.iter()
-            .filter(|a| {
-                // filter the directories that name start with . out
-                // meanwhile filter the no-directories out.
-                !a.as_path()
-                    .file_name()
-                    .expect(&lh::build_language_0("get-file-name-failed"))
-                    .to_str()
-                    .expect(
-                        &lh::build_language_0("osstring-to-string-failed"))
-                    .starts_with(".")
-                    && a.metadata()
-                    .expect(&lh::build_language_1(
-                                    "get-metadata-error",
-                                    "dir_path",
-                                    dir_path.to_str()
-                                ))
-                    .is_dir()
-            })
-        .filter(|a| {
+        let mut sub_dirs = Vec::<PathBuf>::new();
+        for a in sub_items {
+            let file_name = a.file_name()
+                .context(PathFileNameSnafu {
+                    dynamic_errmsg: msg::get_file_name_failed()
+                })?
+                .to_string_lossy()
+                .into_owned();
+            // filter the directories that name start with . out
+            if file_name.starts_with('.') {
+                continue;
+            }
             // excluding the `target` and `src` directories
-            let file_name = a.as_path().file_name()
-                .expect(&lh::build_language_0("get-file-name-failed"))
-                .to_str()
-                .expect(&lh::build_language_0("osstring-to-string-failed"));
-            if marked_cargo_dir {
-                file_name != "target" && file_name != "src"
-            } else {
-                true
+            if marked_cargo_dir && (file_name == "target" || file_name == "src") {
+                continue;
             }
-        })
-        .map(|a| {
-            let mut p = dir_pathes.last().unwrap().clone();
-            p.push(a);
-            p
-        })
-        .collect::<Vec<PathBuf>>();
+            // meanwhile filter the no-directories out.
+            let metadata = match a.metadata()
+                .context(MetadataSnafu {
+                    dynamic_errmsg: msg::get_metadata_error(a.to_string_lossy().into_owned())}) {
+                Ok(o) => o,
+                // like a dangling symlink, only the entry is left out of the walk.
+                Err(e) => {
+                    e.report_warning();
+                    continue;
+                }
+            };
+            if metadata.is_dir() {
+                sub_dirs.push(a);
+            }
+        }
+        let sub_items = sub_dirs;
 
         *dir_sizes.last_mut().unwrap() -= 1;
         dir_sizes.push(sub_items.len() + dir_sizes.last().unwrap());
@@ -129,20 +132,22 @@ fn get_cargo_directories(path_str: &str) -> Result<Vec<PathBuf>> {
         dir_pathes.extend(sub_items);
     }
 
-    return Ok(marked_pathes);
+    Ok(marked_pathes)
 }
 
 #[derive(PartialEq, Debug, Default, Clone, Copy, Eq, PartialOrd, Ord, )]
-enum GeneratingType {
+pub(crate) enum GeneratingType {
     #[default]
     BashCommands,
     RunAsSubprocess,
     DryRunDebug,
+    Makefile,
+    Ninja,
 }
 
 impl ValueEnum for GeneratingType {
     fn value_variants<'a>() -> &'a [Self] {
-        &[Self::BashCommands, Self::RunAsSubprocess, Self::DryRunDebug]
+        &[Self::BashCommands, Self::RunAsSubprocess, Self::DryRunDebug, Self::Makefile, Self::Ninja]
     }
 
     fn to_possible_value(&self) -> Option<PossibleValue> {
@@ -150,70 +155,75 @@ impl ValueEnum for GeneratingType {
             Self::BashCommands => {
                 PossibleValue::new("bash-commands")
                     .help(
-                        &lh::build_language_0("generate-bash-like-cmds-helper"))
+                        msg::generate_bash_like_cmds_helper())
                     .aliases(["cmd", "cmds", "bash_cmds", "bash_commands"])
             }
             Self::RunAsSubprocess => {
                 PossibleValue::new("run-as-subprocess")
                     .help(
-                        &lh::build_language_0("directly-run-helper"))
+                        msg::directly_run_helper())
                     .aliases(["direct", "subprocess", "directly"])
             }
             Self::DryRunDebug => {
                 PossibleValue::new("dry-run-debug")
-                    .help(&lh::build_language_0("dry-run-helper"))
+                    .help(msg::dry_run_helper())
                     .aliases(["dry_run", "dry-run", "dr"])
             }
+            Self::Makefile => {
+                PossibleValue::new("makefile")
+                    .help(msg::makefile_helper())
+                    .aliases(["make", "mk"])
+            }
+            Self::Ninja => {
+                PossibleValue::new("ninja")
+                    .help(msg::ninja_helper())
+                    .aliases(["build.ninja"])
+            }
         })
     }
 }
 
 
-fn process_dir(cargo_dir: &PathBuf, ge_ty: GeneratingType, subcmd: GeneratingSubcommand, ) -> Result<()> {
+fn process_dir(invocation: &CargoInvocation, ge_ty: GeneratingType, ) -> Result<()> {
     let old_dir = std::env::current_dir()
         .context(CurrentDirSnafu)?;
 
-    let old_dir_str = old_dir.to_str()
-        .expect(&lh::build_language_0("pathbuf-to-str-failed"));
-    let dest_dir_str = cargo_dir.to_str()
-        .expect(&lh::build_language_0("pathbuf-to-str-failed"));
-
-    let subcmd = {
-        let tmp: &'static str = subcmd.into();
-        let s = String::from(tmp);
-        s.to_lowercase()
-    };
+    let old_dir_str = old_dir.to_string_lossy();
+    let dest_dir_str = invocation.dir.to_string_lossy();
 
     match ge_ty {
         GeneratingType::BashCommands => {
-            println!("cd {}", dest_dir_str);
-            println!("cargo {subcmd}");
-            println!("cd {}", old_dir_str);
+            println!("# {}", toolchain::describe_toolchain(&invocation.dir, invocation.toolchain.as_deref()));
+            println!("cd {}", invocation::shell_quote(&dest_dir_str));
+            println!("{}", invocation.command_line());
+            println!("cd {}", invocation::shell_quote(&old_dir_str));
             Ok(())
         },
         GeneratingType::RunAsSubprocess => {
-            env::set_current_dir(cargo_dir)
-                    .context(CurrentDirSnafu)?;
-
-            let output = Command::new("cargo").arg(&subcmd)
+            eprintln!("{}", msg::run_toolchain(dest_dir_str.clone().into_owned(),
+                toolchain::describe_toolchain(&invocation.dir, invocation.toolchain.as_deref())));
+            // not changing the current dir of the process, the projects may run in parallel.
+            let output = invocation.command()
                 .output()
-                .expect(&lh::build_language_1("start-cargo-subcommand-failed", "subcommand", subcmd));
+                .context(StartCargoSnafu {
+                    dynamic_errmsg: msg::start_cargo_subcommand_failed(invocation.command_line()),
+                })?;
             if !output.status.success() {
-                env::set_current_dir(old_dir)
-                    .context(CurrentDirSnafu)?;
-
                 return Err(Error::ProcessExit {
                     code: output.status.code(),
                     stdout: output.stdout,
                     stderr: output.stderr,
                 });
             }
-            env::set_current_dir(old_dir)
-                .context(CurrentDirSnafu)?;
             Ok(())
         },
         GeneratingType::DryRunDebug => {
-            eprintln!("RUN: cargo {subcmd} at {}", dest_dir_str);
+            eprintln!("RUN: {} at {} ({})", invocation.command_line(), dest_dir_str,
+                toolchain::describe_toolchain(&invocation.dir, invocation.toolchain.as_deref()));
+            Ok(())
+        },
+        GeneratingType::Makefile | GeneratingType::Ninja => {
+            // rendered for all the projects at once by `build_files`.
             Ok(())
         }
     }
@@ -222,77 +232,510 @@ fn process_dir(cargo_dir: &PathBuf, ge_ty: GeneratingType, subcmd: GeneratingSub
 #[derive(PartialOrd, PartialEq, Eq, Ord, Debug, ValueEnum, Default, Clone, Copy, IntoStaticStr)]
 enum GeneratingSubcommand {
     #[default]
+    #[value(help = msg::subcommand_clean_helper())]
     Clean,
+    #[value(help = msg::subcommand_update_helper())]
     Update,
+    #[value(help = msg::subcommand_build_helper())]
+    Build,
+    #[value(help = msg::subcommand_check_helper())]
+    Check,
+    #[value(help = msg::subcommand_test_helper())]
+    Test,
+    #[value(help = msg::subcommand_clippy_helper())]
+    Clippy,
+}
+
+impl GeneratingSubcommand {
+    // only `clean` gives the same result in any order, a cycle is reported and the
+    // projects are cleaned in the discovered order.
+    fn tolerates_cycles(self) -> bool {
+        self == Self::Clean
+    }
 }
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about = msg::about(), long_about = None)]
 struct Cli {
     // starting point of directories, would be "./" if it isn't supplied.
+    #[arg(help = msg::target_dir_helper())]
     target_dir: Option<String>,
     // generaty types: bash commands(default), output debug(dry run), direct run as subprocess.
-    #[arg(long = "gt", value_enum, default_value_t)]
+    #[arg(long = "gt", value_enum, default_value_t, help = msg::generating_type_helper())]
     generating_type: GeneratingType,
 
-    #[arg(long = "gs", value_enum, default_value_t)]
+    #[arg(long = "gs", value_enum, default_value_t, help = msg::generating_subcommand_helper())]
     generating_subcommand: GeneratingSubcommand,
 
+    #[command(flatten)]
+    clean: clean::CleanOptions,
+
+    #[arg(long, short = 'v', global = true, help = msg::verbose_helper())]
+    verbose: bool,
+
+    // read before the parsing too, the help is built in this language.
+    #[arg(long, global = true, value_name = "LANG", help = msg::lang_helper())]
+    lang: Option<String>,
+
+    #[arg(long, help = msg::print_lang_search_path_helper())]
+    print_lang_search_path: bool,
+
+    #[arg(long, short = 'i', global = true, help = msg::interactive_helper())]
+    interactive: bool,
+
+    #[arg(long, help = msg::list_helper())]
+    list: bool,
+
+    #[arg(long, requires = "list", help = msg::print0_helper())]
+    print0: bool,
+
+    #[arg(long, requires = "list", help = msg::manifest_paths_helper())]
+    manifest_paths: bool,
+
+    #[arg(long, conflicts_with_all = ["target_dir", "interactive"],
+        help = msg::from_stdin_helper())]
+    from_stdin: bool,
+
+    #[arg(long, short = 'j', default_value_t = 1, help = msg::jobs_helper())]
+    jobs: usize,
+
+    #[command(flatten)]
+    git_filter: git::GitFilter,
+
+    #[arg(long, help = msg::git_commit_helper())]
+    git_commit: bool,
+
+    #[arg(long, value_name = "TOOLCHAIN", help = msg::toolchain_helper())]
+    toolchain: Vec<String>,
+
+    #[arg(long = "env", value_name = "KEY=VALUE", value_parser = invocation::parse_env_pair,
+        help = msg::env_helper())]
+    envs: Vec<(String, String)>,
+
+    #[arg(long, value_name = "FILE", help = msg::env_file_helper())]
+    env_file: Option<PathBuf>,
+
+    #[arg(long, value_name = "PATH", help = msg::cargo_bin_helper())]
+    cargo_bin: Option<String>,
+
+    #[arg(long, value_enum, help = msg::report_helper())]
+    report: Option<report::ReportFormat>,
+
+    #[arg(long, value_name = "FILE", requires = "report", help = msg::report_output_helper())]
+    report_output: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    #[command(about = msg::inventory_about())]
+    Inventory {
+        #[arg(long, value_enum, default_value_t,
+            help = msg::inventory_format_helper())]
+        format: inventory::InventoryFormat,
+
+        #[arg(long, help = msg::inventory_multi_major_only_helper())]
+        multi_major_only: bool,
+    },
+    #[command(about = msg::audit_about())]
+    Audit {
+        #[arg(long, help = msg::audit_db_helper())]
+        db: Option<PathBuf>,
+
+        #[arg(long, help = msg::audit_no_yanked_helper())]
+        no_yanked: bool,
+
+        #[arg(long, help = msg::audit_deny_warnings_helper())]
+        deny_warnings: bool,
+    },
+    #[command(about = msg::install_data_about())]
+    InstallData {
+        #[arg(long, value_name = "PREFIX", help = msg::install_data_prefix_helper())]
+        prefix: Option<PathBuf>,
+
+        #[arg(long, help = msg::install_data_uninstall_helper())]
+        uninstall: bool,
+
+        #[arg(long, help = msg::install_data_completions_helper())]
+        completions: bool,
+
+        #[arg(long, help = msg::install_data_man_helper())]
+        man: bool,
+    },
+    #[command(about = msg::completions_about())]
+    Completions {
+        #[arg(value_enum, help = msg::completions_shell_helper())]
+        shell: clap_complete::Shell,
+    },
+    #[command(about = msg::man_about())]
+    Man,
+    #[command(about = msg::doctor_about())]
+    Doctor,
+    #[command(about = msg::validate_about())]
+    Validate,
+}
+
+// the expected failures end the program with a diagnostic instead of a panic.
+fn exit_with_error(e: Error, verbose: bool) -> ! {
+    e.report(verbose);
+    std::process::exit(2);
+}
+
+// the value of `--lang`, before clap builds the help out of the messages.
+fn lang_from_args(args: impl Iterator<Item = OsString>) -> Option<String> {
+    let mut args = args.skip(1);
+    while let Some(arg) = args.next() {
+        let arg = arg.to_string_lossy();
+        if arg == "--" {
+            break;
+        }
+        if arg == "--lang" {
+            return args.next().map(|a| a.to_string_lossy().into_owned());
+        }
+        if let Some(lang) = arg.strip_prefix("--lang=") {
+            return Some(lang.to_owned());
+        }
+    }
+    None
 }
 
 fn main() {
-    lh::init_lang(None, None);
+    lh::init_lang(lang_from_args(env::args_os()), None);
 
-    let cli = Cli::parse();
+    let cli = localized_cli::parse::<Cli>();
+    if cli.print_lang_search_path {
+        lh::print_lang_search_path(None);
+        return;
+    }
+    let verbose = cli.verbose;
+    if verbose && lh::format_error_mode() < lh::FormatErrorMode::Warn {
+        lh::set_format_error_mode(lh::FormatErrorMode::Warn);
+    }
+    // the commands which don't act on the projects.
+    match &cli.command {
+        Some(Commands::InstallData { prefix, uninstall, completions, man }) => {
+            let options = install_data::InstallData {
+                prefix: prefix.clone(),
+                uninstall: *uninstall,
+                completions: *completions,
+                man: *man,
+            };
+            if let Err(e) = install_data::run_install_data(&options, localized_cli::localized_command::<Cli>()) {
+                exit_with_error(e, verbose)
+            }
+            return;
+        },
+        Some(Commands::Completions { shell }) => {
+            let script = shell_integration::render_completions(localized_cli::localized_command::<Cli>(), *shell);
+            if let Err(e) = shell_integration::write_stdout(&script) {
+                exit_with_error(e, verbose)
+            }
+            return;
+        },
+        Some(Commands::Man) => {
+            let page = shell_integration::render_man(localized_cli::localized_command::<Cli>())
+                .and_then(|a| shell_integration::write_stdout(&a));
+            if let Err(e) = page {
+                exit_with_error(e, verbose)
+            }
+            return;
+        },
+        _ => {},
+    }
     let path_str = cli.target_dir.unwrap_or("./".to_string());
     let ge_ty = cli.generating_type;
 
     let mut failed_list = Vec::new();
 
+    let discovered = if cli.from_stdin {
+        project_list::read_project_list(std::io::stdin().lock())
+    } else {
+        get_cargo_directories(&path_str)
+    };
     let marked_pathes =
-        match get_cargo_directories(&path_str) {
+        match discovered {
             Ok(o) => {
                 o
             },
             Err(e) => {
-                panic!("{:?}", e)
+                exit_with_error(e, verbose)
             }
         };
 
+    // a `Cargo.toml` cargo can't read, like an empty one or a template, is left out.
+    let discovered_count = marked_pathes.len();
+    let (mut marked_pathes, invalid_manifests) = validation::partition_manifests(marked_pathes);
+    if !matches!(cli.command, Some(Commands::Validate | Commands::Doctor)) {
+        validation::report_skipped(&invalid_manifests);
+    }
+
+    marked_pathes = match git::filter_projects(marked_pathes, &cli.git_filter) {
+        Ok(o) => o,
+        Err(e) => {
+            exit_with_error(e, verbose)
+        }
+    };
+
+    if cli.interactive {
+        marked_pathes = match selection::select_projects(marked_pathes) {
+            Ok(o) => o,
+            Err(e) => {
+                exit_with_error(e, verbose)
+            }
+        };
+    }
+
+    if cli.list {
+        if let Err(e) = project_list::write_project_list(&marked_pathes, cli.manifest_paths, cli.print0) {
+            exit_with_error(e, verbose)
+        }
+        return;
+    }
+
+    // the `--env` pairs override the ones of `--env-file`, `doctor` reports what the runs get.
+    let envs = {
+        let mut envs = match &cli.env_file {
+            Some(f) => {
+                match invocation::read_env_file(f) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        exit_with_error(e, verbose)
+                    }
+                }
+            },
+            None => vec![],
+        };
+        for (k, v) in &cli.envs {
+            envs.retain(|a| &a.0 != k);
+            envs.push((k.clone(), v.clone()));
+        }
+        envs
+    };
+
+    if let Some(command) = &cli.command {
+        match command {
+            Commands::Inventory { format, multi_major_only } => {
+                if let Err(e) = inventory::run_inventory(&marked_pathes, *format, *multi_major_only) {
+                    exit_with_error(e, verbose)
+                }
+            },
+            Commands::Audit { db, no_yanked, deny_warnings } => {
+                let db_dir = db.clone()
+                    .or_else(audit::default_advisory_db)
+                    .context(NoDefaultAdvisoryDbSnafu)
+                    .unwrap_or_else(|e| exit_with_error(e, verbose));
+                match audit::run_audit(&marked_pathes, &db_dir, !*no_yanked, *deny_warnings) {
+                    Ok(true) => {
+                        std::process::exit(1);
+                    },
+                    Ok(false) => {},
+                    Err(e) => {
+                        exit_with_error(e, verbose)
+                    }
+                }
+            },
+            Commands::Doctor => {
+                // absolute, for the paste.
+                let root = fs::canonicalize(&path_str).unwrap_or_else(|_| PathBuf::from(&path_str));
+                // the cargo of the runs, with the first toolchain listed.
+                let cargo = CargoInvocation::new(&root, "--version")
+                    .program(cli.cargo_bin.as_deref())
+                    .envs(&envs)
+                    .toolchain(cli.toolchain.first().map(|a| a.as_str()));
+                if doctor::run_doctor(&root, discovered_count, &invalid_manifests, &cargo) {
+                    std::process::exit(1);
+                }
+            },
+            Commands::Validate => {
+                if validation::run_validate(discovered_count, &invalid_manifests) {
+                    std::process::exit(1);
+                }
+            },
+            Commands::InstallData { .. } | Commands::Completions { .. } | Commands::Man => {
+                // run before the discovery.
+            },
+        }
+        return;
+    }
+
     if ge_ty == GeneratingType::BashCommands
         || ge_ty == GeneratingType::DryRunDebug {
-            let msg_key = "root-path";
-            let mut args_pairs = vec![];
-
-            let args_pair = (
-                    "root_path",
-                    FluentValue::from({
-                            let t = fs::canonicalize(&path_str)
-                                .unwrap();
-                            let t = t.as_path()
-                                .to_str()
-                                .unwrap();
-                            t.to_owned()
-                        })
-                );
-            args_pairs.push(args_pair);
-            println!("{}", lh::build_language(msg_key, args_pairs));
+            let root_path = fs::canonicalize(&path_str)
+                .context(CanonilizingSnafu {
+                    dynamic_errmsg: msg::file_path_canonicalized_failed(path_str.clone())
+                })
+                .unwrap_or_else(|e| exit_with_error(e, verbose));
+            println!("{}", msg::root_path(root_path.to_string_lossy().into_owned()));
     }
 
-    marked_pathes
-        .iter()
-        .for_each(|a| {
-            match process_dir(a, ge_ty.clone(), cli.generating_subcommand) {
+    // act on the path dependencies before the projects depending on them.
+    let mut graph = dep_graph::DependencyGraph::build(marked_pathes);
+    let order = match graph.topological_order() {
+        Ok(o) => o,
+        // the cycle is only reported, the discovered order is kept.
+        Err(e) if cli.generating_subcommand.tolerates_cycles() => {
+            e.report_warning();
+            graph.clear_dependencies();
+            (0..graph.projects.len()).collect()
+        },
+        Err(e) => {
+            exit_with_error(e, verbose)
+        }
+    };
+
+    // only the repositories clean before the run get the updated lock files committed.
+    let commit_lock = cli.git_commit && cli.generating_subcommand == GeneratingSubcommand::Update;
+    let clean_repos = if commit_lock {
+        match lock_commit::clean_repos(&graph.projects) {
+            Ok(o) => o,
+            Err(e) => {
+                exit_with_error(e, verbose)
+            }
+        }
+    } else {
+        Default::default()
+    };
+
+    let subcmd = {
+        let tmp: &'static str = cli.generating_subcommand.into();
+        tmp.to_lowercase()
+    };
+    // the options of `cargo clean`, and the removals done instead of it.
+    let is_clean = cli.generating_subcommand == GeneratingSubcommand::Clean;
+    if !is_clean && cli.clean.is_active() {
+        eprintln!("{}", msg::clean_options_need_clean());
+    }
+    let clean_args = if is_clean { cli.clean.cargo_args() } else { vec![] };
+    let clean_only = cli.clean.clean_only.filter(|_| is_clean);
+    if clean_only.is_some() && (ge_ty == GeneratingType::Makefile || ge_ty == GeneratingType::Ninja) {
+        exit_with_error(CleanOnlyInBuildFileSnafu.build(), verbose)
+    }
+    // the whole tree runs once for every listed toolchain, the default one when none listed.
+    // the removals of `--clean-only` don't depend on the toolchain.
+    let toolchains = if cli.toolchain.is_empty() || clean_only.is_some() {
+        vec![None]
+    } else {
+        cli.toolchain.iter().map(|a| Some(a.as_str())).collect()
+    };
+
+    if ge_ty == GeneratingType::Makefile || ge_ty == GeneratingType::Ninja {
+        let invocations = toolchains.iter()
+            .map(|t| {
+                graph.projects.iter()
+                    .map(|p| {
+                        CargoInvocation::new(p, &subcmd)
+                            .program(cli.cargo_bin.as_deref())
+                            .envs(&envs)
+                            .toolchain(*t)
+                            .args(&clean_args)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let root = fs::canonicalize(&path_str)
+            .unwrap_or_else(|_| PathBuf::from(&path_str));
+        if ge_ty == GeneratingType::Makefile {
+            print!("{}", build_files::render_makefile(&root, &graph, &order, &invocations));
+        } else {
+            print!("{}", build_files::render_ninja(&root, &graph, &order, &invocations));
+        }
+        return;
+    }
+
+    // the report of the runs, only the subprocesses have outcomes to report.
+    let report_format = match cli.report {
+        Some(_) if ge_ty != GeneratingType::RunAsSubprocess => {
+            eprintln!("{}", msg::report_needs_subprocess());
+            None
+        },
+        f => f,
+    };
+    let mut results = vec![];
+    for toolchain in toolchains {
+        let act = |i: usize| -> (report::RunRecord, Result<()>) {
+            let project = &graph.projects[i];
+            let invocation = CargoInvocation::new(project, &subcmd)
+                .program(cli.cargo_bin.as_deref())
+                .envs(&envs)
+                .toolchain(toolchain)
+                .args(&clean_args);
+            let prune = clean_only.map(|only| clean::Prune::plan(project, only, &cli.clean, &envs));
+            // for the report and the summary, only the subprocesses change the projects.
+            let before = (ge_ty == GeneratingType::RunAsSubprocess)
+                .then(|| report::ProjectSnapshot::take(project, &subcmd, &envs));
+            let started = Instant::now();
+            let r = (|| {
+                let lock_commit = if commit_lock {
+                    lock_commit::LockCommit::prepare(project, &clean_repos)?
+                } else {
+                    None
+                };
+                match &prune {
+                    Some(p) => p.run(ge_ty)?,
+                    None => process_dir(&invocation, ge_ty)?,
+                }
+                if let Some(c) = lock_commit {
+                    c.finish(&invocation, ge_ty)?;
+                }
+                Ok(())
+            })();
+            let duration = started.elapsed();
+            let (reclaimed_bytes, lock_changes) = match &before {
+                Some(b) => b.changes_to(&report::ProjectSnapshot::take(project, &subcmd, &envs)),
+                None => (None, vec![]),
+            };
+            let record = report::RunRecord {
+                project: project.clone(),
+                toolchain: invocation.toolchain.clone(),
+                toolchain_description: toolchain::describe_toolchain(project, invocation.toolchain.as_deref()),
+                action: prune.as_ref().map_or_else(|| invocation.command_line(), |a| a.command_line()),
+                duration,
+                reclaimed_bytes,
+                lock_changes,
+                failure: r.as_ref().err().map(report::RunFailure::from_error),
+            };
+            (record, r)
+        };
+
+        if ge_ty == GeneratingType::RunAsSubprocess && cli.jobs > 1 {
+            results.extend(scheduler::run_in_dependency_order(&graph, cli.jobs, act));
+        } else {
+            results.extend(order.iter().map(|i| (*i, act(*i))));
+        }
+    }
+
+    if let Some(format) = report_format {
+        let records = results.iter()
+            .map(|(_, (record, _))| record.clone())
+            .collect::<Vec<_>>();
+        if let Err(e) = report::write_report(&records, format, cli.report_output.as_deref()) {
+            exit_with_error(e, verbose)
+        }
+    }
+
+    // printed after the failures, the last thing on the terminal.
+    let failures = results.iter()
+        .filter(|(_, (_, r))| r.is_err())
+        .count();
+    let reclaimed = results.iter()
+        .filter_map(|(_, (record, _))| record.reclaimed_bytes)
+        .reduce(|a, b| a + b);
+    // a run per project and toolchain.
+    let total = results.len();
+
+    // every error is reported, the other projects' failures and the summary still follow.
+    let mut errors = 0;
+    results
+        .into_iter()
+        .for_each(|(_, (_, r))| {
+            match r {
                 Ok(_) => {
                     // printed/start processes in function `process_dir`
                 },
                 Err(pde) => {
                     match pde {
-                        Error::CurrentDir {
-                            ..
-                        } => {
-                            panic!("read/set current dir failed: {:?}", pde);
-                        },
                         Error::ProcessExit {
                             ..
                         } => {
@@ -300,34 +743,49 @@ fn main() {
                             failed_list.push(pde);
                         }
                         _ => {
-                            panic!("{:?}", pde)
+                            pde.report(verbose);
+                            errors += 1;
                         }
                     }
                 }
             }
     });
 
-    match ge_ty {
-        GeneratingType::RunAsSubprocess => {
-            failed_list.iter().for_each(|a| {
-                match a {
-                    Error::ProcessExit {
-                        code,
-                        stdout,
-                        stderr,
-                    } => {
-                        println!("{{");
-                        println!("  code: {}", code.map_or_else(|| "None".to_owned(), |a| {format!("{}", a)}));
-                        println!("  stdout: {:?}", String::from_utf8_lossy(stdout));
-                        println!("  stderr: {:?}", String::from_utf8_lossy(stderr));
-                        println!("}}");
-                    }
-                    _ => {
-                        // do nothing
-                    }
+    // the report on stdout already holds the failures.
+    let report_on_stdout = report_format.is_some() && cli.report_output.is_none();
+    if ge_ty == GeneratingType::RunAsSubprocess && !report_on_stdout {
+        failed_list.iter().for_each(|a| {
+            match a {
+                Error::ProcessExit {
+                    code,
+                    stdout,
+                    stderr,
+                } => {
+                    println!("{{");
+                    println!("  code: {}", code.map_or_else(|| "None".to_owned(), |a| {format!("{}", a)}));
+                    println!("  stdout: {:?}", String::from_utf8_lossy(stdout));
+                    println!("  stderr: {:?}", String::from_utf8_lossy(stderr));
+                    println!("}}");
                 }
-            });
+                _ => {
+                    // do nothing
+                }
+            }
+        });
+    }
+
+    if ge_ty == GeneratingType::RunAsSubprocess {
+        eprintln!("{}", msg::run_summary(total, failures));
+        if let Some(bytes) = reclaimed {
+            eprintln!("{}", msg::run_reclaimed(bytes));
         }
-        _ => {}
+    }
+    // like `exit_with_error` for the errors, like `audit` for a failed cargo process,
+    // the run fails for the scripts and the CI.
+    if errors > 0 {
+        std::process::exit(2);
+    }
+    if failures > 0 {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,315 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::messages as msg;
+
+pub static MANIFEST_FILE_NAME: &str = "Cargo.toml";
+pub static LOCK_FILE_NAME: &str = "Cargo.lock";
+
+// tables in a manifest which declare dependencies.
+static DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    // `None` for the path/workspace crates, those are the projects themselves.
+    pub source: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct LockFile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeclaredDependency {
+    // the real crate name, the key in `[dependencies]` may be renamed by `package = "..."`.
+    pub package: String,
+    pub requirement: Option<String>,
+    pub path: Option<String>,
+    // from `[dev-dependencies]`, only the tests and the examples need it.
+    pub dev: bool,
+}
+
+pub fn manifest_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(MANIFEST_FILE_NAME)
+}
+
+fn read_to_string(file_path: &Path) -> Result<String> {
+    fs::read_to_string(file_path)
+        .context(ReadFileSnafu {
+            dynamic_errmsg: msg::read_file_failed(file_path.to_string_lossy().into_owned()),
+        })
+}
+
+pub fn read_manifest(project_dir: &Path) -> Result<toml::Table> {
+    let manifest_path = manifest_path(project_dir);
+    let content = read_to_string(&manifest_path)?;
+    content.parse::<toml::Table>()
+        .context(ParseTomlSnafu {
+            dynamic_errmsg: msg::parse_toml_failed(manifest_path.to_string_lossy().into_owned()),
+        })
+}
+
+/// The root of the workspace the project is a member of, the project itself when it's in none.
+/// Like cargo, `package.workspace` is followed, or the parents are searched for a `[workspace]`
+/// which doesn't exclude the project.
+pub fn workspace_root(project_dir: &Path) -> PathBuf {
+    let project_dir = fs::canonicalize(project_dir).unwrap_or_else(|_| project_dir.to_path_buf());
+    let Ok(m) = read_manifest(&project_dir) else { return project_dir; };
+    if m.contains_key("workspace") {
+        return project_dir;
+    }
+    if let Some(p) = m.get("package").and_then(|a| a.get("workspace")).and_then(|a| a.as_str()) {
+        return project_dir.join(p);
+    }
+    for dir in project_dir.ancestors().skip(1) {
+        if !manifest_path(dir).is_file() {
+            continue;
+        }
+        let Ok(m) = read_manifest(dir) else { continue; };
+        let Some(ws) = m.get("workspace") else { continue; };
+        let excluded = ws.get("exclude")
+            .and_then(|a| a.as_array())
+            .is_some_and(|a| a.iter()
+                .filter_map(|b| b.as_str())
+                .any(|b| project_dir.starts_with(dir.join(b))));
+        if excluded {
+            break;
+        }
+        return dir.to_path_buf();
+    }
+    project_dir
+}
+
+/// Reads the `Cargo.lock` beside the manifest, `None` if the project has no lock file.
+pub fn read_lockfile(project_dir: &Path) -> Result<Option<Vec<LockedPackage>>> {
+    let lock_path = project_dir.join(LOCK_FILE_NAME);
+    if !lock_path.is_file() {
+        return Ok(None);
+    }
+    let content = read_to_string(&lock_path)?;
+    let lock: LockFile = toml::from_str(&content)
+        .context(ParseTomlSnafu {
+            dynamic_errmsg: msg::parse_toml_failed(lock_path.to_string_lossy().into_owned()),
+        })?;
+    Ok(Some(lock.package))
+}
+
+fn dependencies_of_table(table: &toml::Table, deps: &mut Vec<DeclaredDependency>) {
+    for table_name in DEPENDENCY_TABLES {
+        let Some(toml::Value::Table(dep_table)) = table.get(table_name) else {
+            continue;
+        };
+        for (key, value) in dep_table {
+            let dep = match value {
+                toml::Value::String(req) => {
+                    DeclaredDependency {
+                        package: key.clone(),
+                        requirement: Some(req.clone()),
+                        path: None,
+                        dev: table_name == "dev-dependencies",
+                    }
+                },
+                toml::Value::Table(detail) => {
+                    let get_str = |name: &str| {
+                        detail.get(name)
+                            .and_then(|a| a.as_str())
+                            .map(|a| a.to_owned())
+                    };
+                    DeclaredDependency {
+                        package: get_str("package").unwrap_or(key.clone()),
+                        requirement: get_str("version"),
+                        path: get_str("path"),
+                        dev: table_name == "dev-dependencies",
+                    }
+                },
+                _ => {
+                    continue;
+                }
+            };
+            deps.push(dep);
+        }
+    }
+}
+
+/// Collects the dependencies declared in every dependency table of a manifest,
+/// including the platform specific `[target.'cfg(..)'.dependencies]` ones.
+pub fn declared_dependencies(manifest: &toml::Table) -> Vec<DeclaredDependency> {
+    let mut deps = vec![];
+    dependencies_of_table(manifest, &mut deps);
+    if let Some(toml::Value::Table(targets)) = manifest.get("target") {
+        for target_table in targets.values() {
+            if let toml::Value::Table(t) = target_table {
+                dependencies_of_table(t, &mut deps);
+            }
+        }
+    }
+    deps
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LockChange {
+    Updated { name: String, from: String, to: String },
+    Added { name: String, version: String },
+    Removed { name: String, version: String },
+}
+
+impl std::fmt::Display for LockChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockChange::Updated { name, from, to } => write!(f, "{name} {from} -> {to}"),
+            LockChange::Added { name, version } => write!(f, "{name} {version} (added)"),
+            LockChange::Removed { name, version } => write!(f, "{name} {version} (removed)"),
+        }
+    }
+}
+
+/// Compares the packages of two lock files by name, a single version replaced
+/// by another is an update, the rest are additions and removals.
+pub fn diff_locked_packages(before: &[LockedPackage], after: &[LockedPackage]) -> Vec<LockChange> {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let versions_by_name = |packages: &[LockedPackage]| {
+        let mut m = BTreeMap::<String, BTreeSet<String>>::new();
+        for p in packages {
+            m.entry(p.name.clone()).or_default().insert(p.version.clone());
+        }
+        m
+    };
+    let before = versions_by_name(before);
+    let after = versions_by_name(after);
+    let empty = BTreeSet::new();
+
+    let mut changes = vec![];
+    for name in before.keys().chain(after.keys()).collect::<BTreeSet<_>>() {
+        let b = before.get(name).unwrap_or(&empty);
+        let a = after.get(name).unwrap_or(&empty);
+        let removed = b.difference(a).collect::<Vec<_>>();
+        let added = a.difference(b).collect::<Vec<_>>();
+        if removed.len() == 1 && added.len() == 1 {
+            changes.push(LockChange::Updated {
+                name: name.clone(),
+                from: removed[0].clone(),
+                to: added[0].clone(),
+            });
+            continue;
+        }
+        changes.extend(removed.into_iter().map(|v| LockChange::Removed { name: name.clone(), version: v.clone() }));
+        changes.extend(added.into_iter().map(|v| LockChange::Added { name: name.clone(), version: v.clone() }));
+    }
+    changes
+}
+
+/// Parses the `Updating foo v1.0.0 -> v1.0.1`, `Adding`, `Removing` lines
+/// `cargo update` writes to stderr, the way to know the changes of `--dry-run`.
+pub fn parse_cargo_update_output(stderr: &str) -> Vec<LockChange> {
+    let strip_v = |a: &str| a.strip_prefix('v').unwrap_or(a).to_owned();
+    stderr.lines()
+        .filter_map(|line| {
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            match words.as_slice() {
+                ["Updating" | "Downgrading", name, from, "->", to, ..] => {
+                    Some(LockChange::Updated { name: name.to_string(), from: strip_v(from), to: strip_v(to) })
+                },
+                ["Adding", name, version, ..] => {
+                    Some(LockChange::Added { name: name.to_string(), version: strip_v(version) })
+                },
+                ["Removing", name, version, ..] => {
+                    Some(LockChange::Removed { name: name.to_string(), version: strip_v(version) })
+                },
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(name: &str, version: &str) -> LockedPackage {
+        LockedPackage {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            source: Some("registry+https://github.com/rust-lang/crates.io-index".to_owned()),
+        }
+    }
+
+    fn updated(name: &str, from: &str, to: &str) -> LockChange {
+        LockChange::Updated { name: name.to_owned(), from: from.to_owned(), to: to.to_owned() }
+    }
+
+    fn added(name: &str, version: &str) -> LockChange {
+        LockChange::Added { name: name.to_owned(), version: version.to_owned() }
+    }
+
+    fn removed(name: &str, version: &str) -> LockChange {
+        LockChange::Removed { name: name.to_owned(), version: version.to_owned() }
+    }
+
+    #[test]
+    fn diff_locked_packages_by_name() {
+        let before = [locked("serde", "1.0.1"), locked("syn", "1.0.0"), locked("syn", "2.0.0"), locked("libc", "0.2.0")];
+        let after = [locked("serde", "1.0.2"), locked("syn", "2.0.1"), locked("memchr", "2.7.0"), locked("libc", "0.2.0")];
+        assert_eq!(diff_locked_packages(&before, &after), vec![
+            added("memchr", "2.7.0"),
+            updated("serde", "1.0.1", "1.0.2"),
+            removed("syn", "1.0.0"),
+            removed("syn", "2.0.0"),
+            added("syn", "2.0.1"),
+        ]);
+    }
+
+    #[test]
+    fn diff_locked_packages_of_a_new_lock_file() {
+        let after = [locked("a", "0.1.0"), locked("serde", "1.0.2")];
+        assert_eq!(diff_locked_packages(&[], &after), vec![added("a", "0.1.0"), added("serde", "1.0.2")]);
+        assert!(diff_locked_packages(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn workspace_root_of_the_members() {
+        let root = std::env::temp_dir().join(format!("rust_recursively_action-ws-{}", std::process::id()));
+        for (dir, manifest) in [
+            ("", "[workspace]\nmembers = [\"m\"]\nexclude = [\"x\"]\n"),
+            ("m", "[package]\nname = \"m\"\n"),
+            ("x", "[package]\nname = \"x\"\n"),
+            ("o/p", "[package]\nname = \"p\"\nworkspace = \"../q\"\n"),
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(manifest_path(&root.join(dir)), manifest).unwrap();
+        }
+        let root = fs::canonicalize(&root).unwrap();
+        let roots = ["", "m", "x", "o/p"].map(|a| workspace_root(&root.join(a)));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(roots, [root.clone(), root.clone(), root.join("x"), root.join("o/p/../q")]);
+    }
+
+    #[test]
+    fn parse_cargo_update_output_lines() {
+        let stderr = "    Updating crates.io index
+     Locking 3 packages to latest compatible versions
+    Updating serde v1.0.1 -> v1.0.2
+ Downgrading syn v2.0.1 -> v2.0.0 (available: v2.0.9)
+      Adding memchr v2.7.0
+    Removing libc v0.2.0
+note: pass `--verbose` to see 2 unchanged dependencies behind latest
+warning: not updating lockfile due to dry run
+";
+        assert_eq!(parse_cargo_update_output(stderr), vec![
+            updated("serde", "1.0.1", "1.0.2"),
+            updated("syn", "2.0.1", "2.0.0"),
+            added("memchr", "2.7.0"),
+            removed("libc", "0.2.0"),
+        ]);
+        assert!(parse_cargo_update_output("    Updating crates.io index\n").is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use fluent::FluentValue;
+
+use crate::language_helpers as lh;
+
+// the variables without a type take any value, the counts are typed
+// so they can be selected on by the plural rules.
+macro_rules! arg_type {
+    ($l:lifetime) => { impl Into<FluentValue<$l>> };
+    ($l:lifetime, $ty:ty) => { $ty };
+}
+
+macro_rules! message {
+    ($name:ident = $key:literal ()) => {
+        pub fn $name() -> String {
+            lh::build_language($key, vec![])
+        }
+    };
+    ($name:ident = $key:literal ($($arg:ident $(: $ty:ty)?),+)) => {
+        // `'a` is unused when every variable is typed.
+        #[allow(clippy::extra_unused_lifetimes)]
+        pub fn $name<'a>($($arg: arg_type!('a $(, $ty)?)),+) -> String {
+            lh::build_language($key, vec![$((stringify!($arg), $arg.into())),+])
+        }
+    };
+}
+
+// one function per message taking its variables, an unknown message or a missing variable
+// fails to compile. The variables are in the order they appear in the en-US message.
+macro_rules! messages {
+    ($( $name:ident = $key:literal ( $($arg:ident $(: $ty:ty)?),* ); )*) => {
+        $(
+            message!($name = $key ($($arg $(: $ty)?),*));
+        )*
+
+        /// Every message with its variables, the language files are checked against it in the strict mode.
+        pub static CATALOGUE: &[(&str, &[&str])] = &[$(($key, &[$(stringify!($arg)),*])),*];
+    };
+}
+
+messages! {
+    file_path_canonicalized_failed = "file-path-canonicalized-failed"(path_dir);
+    root_path = "root-path"(root_path);
+    read_directory_failed = "read-directory-failed"(dir_path);
+    read_directory_entry_failed = "read-directory-entry-failed"();
+    get_file_name_failed = "get-file-name-failed"();
+    get_metadata_error = "get-metadata-error"(dir_path);
+    generate_bash_like_cmds_helper = "generate-bash-like-cmds-helper"();
+    directly_run_helper = "directly-run-helper"();
+    dry_run_helper = "dry-run-helper"();
+    start_cargo_subcommand_failed = "start-cargo-subcommand-failed"(subcommand);
+    read_file_failed = "read-file-failed"(file_path);
+    parse_toml_failed = "parse-toml-failed"(file_path);
+    inventory_about = "inventory-about"();
+    inventory_format_helper = "inventory-format-helper"();
+    inventory_multi_major_only_helper = "inventory-multi-major-only-helper"();
+    inventory_multi_major = "inventory-multi-major"(crate_name, series);
+    audit_about = "audit-about"();
+    audit_db_helper = "audit-db-helper"();
+    audit_no_yanked_helper = "audit-no-yanked-helper"();
+    audit_deny_warnings_helper = "audit-deny-warnings-helper"();
+    audit_no_default_db = "audit-no-default-db"();
+    audit_skip_advisory = "audit-skip-advisory"(file_path);
+    audit_vulnerability = "audit-vulnerability"(advisory_id, crate_name, version, title, patched);
+    audit_informational = "audit-informational"(advisory_id, kind, crate_name, version, title);
+    audit_yanked = "audit-yanked"(crate_name, version);
+    audit_summary = "audit-summary"(vulnerabilities: usize, warnings: usize, projects: usize);
+    interactive_helper = "interactive-helper"();
+    selection_item = "selection-item"(project, bytes: u64, age);
+    selection_checklist_prompt = "selection-checklist-prompt"();
+    selection_numbered_prompt = "selection-numbered-prompt"();
+    selection_invalid_answer = "selection-invalid-answer"(answer);
+    age_unknown = "age-unknown"();
+    age_minutes = "age-minutes"(minutes: u64);
+    age_hours = "age-hours"(hours: u64);
+    age_days = "age-days"(days: u64);
+    jobs_helper = "jobs-helper"();
+    git_dirty_only_helper = "git-dirty-only-helper"();
+    git_clean_only_helper = "git-clean-only-helper"();
+    skip_untracked_repos_helper = "skip-untracked-repos-helper"();
+    changed_since_helper = "changed-since-helper"();
+    start_git_failed = "start-git-failed"(dir_path);
+    git_commit_helper = "git-commit-helper"();
+    git_commit_skip_dirty = "git-commit-skip-dirty"(repo);
+    git_commit_nothing = "git-commit-nothing"(project);
+    git_commit_new_lock = "git-commit-new-lock"(project);
+    git_commit_done = "git-commit-done"(project, repo);
+    toolchain_helper = "toolchain-helper"();
+    toolchain_overridden = "toolchain-overridden"(toolchain, file_toolchain, file_path);
+    toolchain_explicit = "toolchain-explicit"(toolchain);
+    toolchain_from_file = "toolchain-from-file"(toolchain, file_path);
+    toolchain_default = "toolchain-default"();
+    run_toolchain = "run-toolchain"(project, toolchain);
+    env_helper = "env-helper"();
+    env_file_helper = "env-file-helper"();
+    cargo_bin_helper = "cargo-bin-helper"();
+    env_pair_invalid = "env-pair-invalid"(pair);
+    env_name_invalid = "env-name-invalid"(name);
+    makefile_helper = "makefile-helper"();
+    ninja_helper = "ninja-helper"();
+    list_helper = "list-helper"();
+    print0_helper = "print0-helper"();
+    manifest_paths_helper = "manifest-paths-helper"();
+    from_stdin_helper = "from-stdin-helper"();
+    not_a_cargo_project = "not-a-cargo-project"(path);
+    report_helper = "report-helper"();
+    report_output_helper = "report-output-helper"();
+    report_needs_subprocess = "report-needs-subprocess"();
+    write_file_failed = "write-file-failed"(file_path);
+    report_title = "report-title"();
+    report_summary = "report-summary"(total: usize, failures: usize);
+    report_project = "report-project"();
+    report_action = "report-action"();
+    report_toolchain = "report-toolchain"();
+    report_status = "report-status"();
+    report_duration = "report-duration"();
+    report_changes = "report-changes"();
+    report_passed = "report-passed"();
+    report_failed = "report-failed"(reason);
+    report_reclaimed = "report-reclaimed"(bytes: u64);
+    run_summary = "run-summary"(total: usize, failures: usize);
+    run_reclaimed = "run-reclaimed"(bytes: u64);
+    report_failures = "report-failures"();
+    verbose_helper = "verbose-helper"();
+    lang_helper = "lang-helper"();
+    error_caused_by = "error-caused-by"();
+    error_hint = "error-hint"();
+    error_location = "error-location"();
+    error_backtrace = "error-backtrace"();
+    error_current_dir = "error-current-dir"();
+    error_process_exit = "error-process-exit"(code);
+    error_process_killed = "error-process-killed"();
+    error_language_files_not_found = "error-language-files-not-found"(path);
+    error_language_negotiated = "error-language-negotiated"(lang);
+    error_advisory_db_not_found = "error-advisory-db-not-found"(path);
+    error_std_io = "error-std-io"();
+    error_selection_prompt = "error-selection-prompt"();
+    error_dependency_cycle = "error-dependency-cycle"(cycle);
+    error_git_command = "error-git-command"(args, dir_path);
+    error_env_file_line = "error-env-file-line"(line_number, file_path, line);
+    error_serialize_json = "error-serialize-json"();
+    hint_canonicalizing = "hint-canonicalizing"();
+    hint_report_bug = "hint-report-bug"();
+    hint_read_dir = "hint-read-dir"();
+    hint_path_file_name = "hint-path-file-name"();
+    hint_current_dir = "hint-current-dir"();
+    hint_process_exit = "hint-process-exit"();
+    hint_language_files = "hint-language-files"();
+    hint_language_negotiated = "hint-language-negotiated"();
+    hint_read_file = "hint-read-file"();
+    hint_write_file = "hint-write-file"();
+    hint_parse_toml = "hint-parse-toml"();
+    hint_advisory_db = "hint-advisory-db"();
+    hint_std_io = "hint-std-io"();
+    hint_selection_prompt = "hint-selection-prompt"();
+    hint_dependency_cycle = "hint-dependency-cycle"();
+    hint_start_git = "hint-start-git"();
+    hint_git_command = "hint-git-command"();
+    hint_env_file_line = "hint-env-file-line"();
+    hint_start_cargo = "hint-start-cargo"();
+    hint_metadata = "hint-metadata"();
+    error_language_io = "error-language-io"(path);
+    error_parse_fluent = "error-parse-fluent"(file_path, errors);
+    error_parse_language_id = "error-parse-language-id"(lang);
+    hint_parse_fluent = "hint-parse-fluent"();
+    about = "about"();
+    target_dir_helper = "target-dir-helper"();
+    generating_type_helper = "generating-type-helper"();
+    generating_subcommand_helper = "generating-subcommand-helper"();
+    subcommand_clean_helper = "subcommand-clean-helper"();
+    subcommand_update_helper = "subcommand-update-helper"();
+    subcommand_build_helper = "subcommand-build-helper"();
+    subcommand_check_helper = "subcommand-check-helper"();
+    subcommand_test_helper = "subcommand-test-helper"();
+    subcommand_clippy_helper = "subcommand-clippy-helper"();
+    report_junit_helper = "report-junit-helper"();
+    report_markdown_helper = "report-markdown-helper"();
+    report_html_helper = "report-html-helper"();
+    inventory_csv_helper = "inventory-csv-helper"();
+    inventory_json_helper = "inventory-json-helper"();
+    help_helper = "help-helper"();
+    version_helper = "version-helper"();
+    help_subcommand_about = "help-subcommand-about"();
+    help_usage_heading = "help-usage-heading"();
+    help_arguments_heading = "help-arguments-heading"();
+    help_options_heading = "help-options-heading"();
+    help_commands_heading = "help-commands-heading"();
+    help_command_value_name = "help-command-value-name"();
+    help_possible_values_heading = "help-possible-values-heading"();
+    help_default_value = "help-default-value"(value);
+    clap_unknown_argument = "clap-unknown-argument"(arg);
+    clap_invalid_value = "clap-invalid-value"(value, arg);
+    clap_value_required = "clap-value-required"(arg);
+    clap_value_validation = "clap-value-validation"(value, arg, reason);
+    clap_invalid_subcommand = "clap-invalid-subcommand"(subcommand);
+    clap_argument_conflict = "clap-argument-conflict"(arg, other);
+    clap_missing_required = "clap-missing-required"(args);
+    clap_similar_argument = "clap-similar-argument"(arg);
+    clap_similar_value = "clap-similar-value"(value);
+    clap_similar_subcommand = "clap-similar-subcommand"(subcommand);
+    clap_pass_as_value = "clap-pass-as-value"(arg);
+    clap_possible_values = "clap-possible-values"(values);
+    clap_more_information = "clap-more-information"();
+    print_lang_search_path_helper = "print-lang-search-path-helper"();
+    lang_search_entry = "lang-search-entry"(index: usize, path, origin, status);
+    lang_search_origin_cwd = "lang-search-origin-cwd"();
+    lang_search_origin_env = "lang-search-origin-env"(var);
+    lang_search_origin_default = "lang-search-origin-default"(var);
+    lang_search_origin_default_or_relative = "lang-search-origin-default-or-relative"(var);
+    lang_search_origin_exe = "lang-search-origin-exe"();
+    lang_search_used = "lang-search-used"();
+    lang_search_shadowed = "lang-search-shadowed"();
+    lang_search_not_set = "lang-search-not-set"();
+    lang_search_relative = "lang-search-relative"();
+    lang_search_missing = "lang-search-missing"();
+    lang_search_not_a_directory = "lang-search-not-a-directory"();
+    lang_search_builtin = "lang-search-builtin"();
+    install_data_about = "install-data-about"();
+    install_data_prefix_helper = "install-data-prefix-helper"();
+    install_data_uninstall_helper = "install-data-uninstall-helper"();
+    install_data_completions_helper = "install-data-completions-helper"();
+    install_data_man_helper = "install-data-man-helper"();
+    install_data_installed = "install-data-installed"(path);
+    install_data_unchanged = "install-data-unchanged"(path);
+    install_data_removed = "install-data-removed"(path);
+    install_data_nothing_removed = "install-data-nothing-removed"(path);
+    install_data_not_searched = "install-data-not-searched"(path);
+    completions_about = "completions-about"();
+    completions_shell_helper = "completions-shell-helper"();
+    man_about = "man-about"();
+    man_section_name = "man-section-name"();
+    man_section_synopsis = "man-section-synopsis"();
+    man_section_description = "man-section-description"();
+    man_section_version = "man-section-version"();
+    man_section_authors = "man-section-authors"();
+    doctor_about = "doctor-about"();
+    doctor_program = "doctor-program"(version);
+    doctor_language_dir = "doctor-language-dir"(path, origin);
+    doctor_language = "doctor-language"(lang, desired, score: u8);
+    doctor_language_builtin = "doctor-language-builtin"(lang);
+    doctor_language_pseudo = "doctor-language-pseudo"(lang);
+    doctor_version = "doctor-version"(program, version);
+    doctor_command_failed = "doctor-command-failed"(command, reason);
+    doctor_variable_set = "doctor-variable-set"(name, value);
+    doctor_variable_not_set = "doctor-variable-not-set"(name);
+    doctor_root_writable = "doctor-root-writable"(path);
+    doctor_root_not_writable = "doctor-root-not-writable"(path, reason);
+    doctor_manifest_broken = "doctor-manifest-broken"(path, problem);
+    doctor_summary = "doctor-summary"(problems: usize);
+    manifest_skipped = "manifest-skipped"(path, problem);
+    manifest_problem_unreadable = "manifest-problem-unreadable"(reason);
+    manifest_problem_empty = "manifest-problem-empty"();
+    manifest_problem_placeholder = "manifest-problem-placeholder"(placeholder);
+    manifest_problem_syntax = "manifest-problem-syntax"(reason);
+    manifest_problem_no_package = "manifest-problem-no-package"();
+    validate_about = "validate-about"();
+    validate_summary = "validate-summary"(projects: usize, invalid: usize);
+    clean_release_helper = "clean-release-helper"();
+    clean_profile_helper = "clean-profile-helper"();
+    clean_target_helper = "clean-target-helper"();
+    clean_doc_helper = "clean-doc-helper"();
+    clean_only_helper = "clean-only-helper"();
+    clean_only_incremental_helper = "clean-only-incremental-helper"();
+    clean_only_stale_deps_helper = "clean-only-stale-deps-helper"();
+    clean_only_nothing = "clean-only-nothing"(project);
+    clean_options_need_clean = "clean-options-need-clean"();
+    error_clean_only_in_build_file = "error-clean-only-in-build-file"();
+    hint_clean_only_in_build_file = "hint-clean-only-in-build-file"();
+    create_directory_failed = "create-directory-failed"(dir_path);
+    remove_file_failed = "remove-file-failed"(file_path);
+    error_no_data_dir = "error-no-data-dir"();
+    hint_no_data_dir = "hint-no-data-dir"();
+    hint_remove_file = "hint-remove-file"();
+}
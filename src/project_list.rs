@@ -0,0 +1,67 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::messages as msg;
+use crate::manifest;
+
+/// Reads the project dirs given on stdin, NUL delimited when the input holds a NUL
+/// (`find -print0`), newline delimited otherwise. A path to a `Cargo.toml` stands
+/// for its directory, the entries which aren't cargo projects are reported and skipped.
+pub fn read_project_list(mut input: impl Read) -> Result<Vec<PathBuf>> {
+    let mut content = Vec::new();
+    input.read_to_end(&mut content)
+        .context(StdIOSnafu)?;
+    let delimiter = if content.contains(&0) { 0 } else { b'\n' };
+
+    let mut projects = vec![];
+    for entry in content.split(|a| *a == delimiter) {
+        let entry = String::from_utf8_lossy(entry);
+        let entry = entry.trim_end_matches('\r');
+        if entry.trim().is_empty() {
+            continue;
+        }
+        let mut path = PathBuf::from(entry);
+        if path.file_name().is_some_and(|a| a == manifest::MANIFEST_FILE_NAME) {
+            path.pop();
+        }
+        match fs::canonicalize(&path) {
+            Ok(p) if p.join(manifest::MANIFEST_FILE_NAME).is_file() => {
+                if !projects.contains(&p) {
+                    projects.push(p);
+                }
+            },
+            _ => {
+                eprintln!("{}", msg::not_a_cargo_project(entry.to_owned()));
+            }
+        }
+    }
+    Ok(projects)
+}
+
+/// Writes only the paths, no header, for `xargs -0`, `fzf` and the scripts.
+pub fn write_project_list(projects: &[PathBuf], manifest_paths: bool, print0: bool) -> Result<()> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for p in projects {
+        let path = if manifest_paths {
+            manifest::manifest_path(p)
+        } else {
+            p.clone()
+        };
+        let write = out.write_all(path.as_os_str().as_encoded_bytes())
+            .and_then(|_| out.write_all(if print0 { b"\0" } else { b"\n" }));
+        if let Err(e) = write {
+            // the reader went away, like `head`.
+            if e.kind() == io::ErrorKind::BrokenPipe {
+                return Ok(());
+            }
+            return Err(e).context(StdIOSnafu);
+        }
+    }
+    out.flush()
+        .context(StdIOSnafu)
+}
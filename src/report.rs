@@ -0,0 +1,363 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::ValueEnum;
+use snafu::prelude::*;
+
+use crate::clean;
+use crate::disk_usage;
+use crate::errors::*;
+use crate::messages as msg;
+use crate::manifest::{self, LockChange, LockedPackage};
+
+// the lines of stderr kept for a failure in the summaries.
+static STDERR_TAIL_LINES: usize = 20;
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq, ValueEnum)]
+pub enum ReportFormat {
+    // JUnit XML, read natively by most CI servers
+    #[value(help = msg::report_junit_helper())]
+    Junit,
+    // a table to paste into the issues
+    #[value(help = msg::report_markdown_helper())]
+    Markdown,
+    // a standalone page for the build artifacts
+    #[value(help = msg::report_html_helper())]
+    Html,
+}
+
+/// The outcome of one cargo run in a project.
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub project: PathBuf,
+    pub toolchain: Option<String>,
+    // the toolchain the run used, from `+TOOLCHAIN` or the toolchain file
+    pub toolchain_description: String,
+    // the command line of the run, like `cargo +nightly test`
+    pub action: String,
+    pub duration: Duration,
+    // the size of `target` freed by a clean
+    pub reclaimed_bytes: Option<u64>,
+    // the entries of `Cargo.lock` changed by an update
+    pub lock_changes: Vec<LockChange>,
+    pub failure: Option<RunFailure>,
+}
+
+/// The state of a project which a run may change, taken before and after the run.
+#[derive(Debug, Default)]
+pub struct ProjectSnapshot {
+    target_bytes: Option<u64>,
+    locked: Option<Vec<LockedPackage>>,
+}
+
+impl ProjectSnapshot {
+    /// Only what the subcommand changes is taken, walking `target` isn't cheap.
+    pub fn take(project: &Path, subcmd: &str, envs: &[(String, String)]) -> Self {
+        match subcmd {
+            "clean" => {
+                Self {
+                    target_bytes: Some(disk_usage::disk_usage(&clean::target_dir(project, envs)).bytes),
+                    ..Default::default()
+                }
+            },
+            "update" => {
+                Self {
+                    locked: manifest::read_lockfile(project).ok().flatten(),
+                    ..Default::default()
+                }
+            },
+            _ => Default::default(),
+        }
+    }
+
+    /// The reclaimed bytes and the changed lock entries from this snapshot to the later one.
+    pub fn changes_to(&self, after: &Self) -> (Option<u64>, Vec<LockChange>) {
+        let reclaimed = match (self.target_bytes, after.target_bytes) {
+            (Some(b), Some(a)) => Some(b.saturating_sub(a)),
+            _ => None,
+        };
+        let lock_changes = match (&self.locked, &after.locked) {
+            (Some(b), Some(a)) => manifest::diff_locked_packages(b, a),
+            _ => vec![],
+        };
+        (reclaimed, lock_changes)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunFailure {
+    // the code of the error, like `RRA0022`, `None` when the cargo process failed
+    pub error_code: Option<&'static str>,
+    pub message: String,
+    // empty for the errors of this program
+    pub stderr: String,
+}
+
+impl RunFailure {
+    /// A failed cargo process, or an error of this program which left the project
+    /// undone, like cargo not starting, the other projects still run.
+    pub fn from_error(e: &Error) -> Self {
+        match e {
+            Error::ProcessExit { stderr, .. } => {
+                Self {
+                    error_code: None,
+                    message: message(e.message()),
+                    stderr: String::from_utf8_lossy(stderr).into_owned(),
+                }
+            },
+            _ => {
+                Self {
+                    error_code: Some(e.code()),
+                    message: message(e.message()),
+                    stderr: String::new(),
+                }
+            }
+        }
+    }
+
+    pub fn stderr_tail(&self) -> String {
+        let lines = self.stderr.trim_end().lines().collect::<Vec<_>>();
+        lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+    }
+}
+
+// cargo colours its output when forced to, the colours are noise in the reports.
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+// XML 1.0 doesn't allow the control chars, cargo colours its output with `ESC`.
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 => {},
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// One testsuite per toolchain, one testcase per project with its path as the classname.
+pub fn render_junit(records: &[RunRecord]) -> String {
+    let mut suites = Vec::<(Option<&str>, Vec<&RunRecord>)>::new();
+    for r in records {
+        match suites.iter_mut().find(|a| a.0 == r.toolchain.as_deref()) {
+            Some(s) => s.1.push(r),
+            None => suites.push((r.toolchain.as_deref(), vec![r])),
+        }
+    }
+    // JUnit tells the failed cargo processes from the errors of this program.
+    let failures_of = |rs: &[&RunRecord]| rs.iter()
+        .filter(|a| a.failure.as_ref().is_some_and(|f| f.error_code.is_none()))
+        .count();
+    let errors_of = |rs: &[&RunRecord]| rs.iter()
+        .filter(|a| a.failure.as_ref().is_some_and(|f| f.error_code.is_some()))
+        .count();
+    let time_of = |rs: &[&RunRecord]| rs.iter().map(|a| a.duration.as_secs_f64()).sum::<f64>();
+
+    let all = records.iter().collect::<Vec<_>>();
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!("<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+        env!("CARGO_PKG_NAME"), all.len(), failures_of(&all), errors_of(&all), time_of(&all)));
+    for (toolchain, rs) in &suites {
+        let name = match toolchain {
+            Some(t) => format!("{} +{}", env!("CARGO_PKG_NAME"), t),
+            None => env!("CARGO_PKG_NAME").to_owned(),
+        };
+        out.push_str(&format!("  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"0\" time=\"{:.3}\">\n",
+            xml_escape(&name), rs.len(), failures_of(rs), errors_of(rs), time_of(rs)));
+        for r in rs {
+            out.push_str(&format!("    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                xml_escape(&r.action), xml_escape(&r.project.to_string_lossy()), r.duration.as_secs_f64()));
+            match &r.failure {
+                Some(f) => {
+                    out.push_str(">\n");
+                    match f.error_code {
+                        Some(code) => {
+                            out.push_str(&format!("      <error message=\"{}\" type=\"{}\"/>\n",
+                                xml_escape(&f.message), code));
+                        },
+                        None => {
+                            out.push_str(&format!("      <failure message=\"{}\" type=\"ProcessExit\">{}</failure>\n",
+                                xml_escape(&f.message), xml_escape(&f.stderr)));
+                        }
+                    }
+                    out.push_str("    </testcase>\n");
+                },
+                None => {
+                    out.push_str("/>\n");
+                }
+            }
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+// fluent wraps the arguments in the bidi isolation marks, they'd show up in the pasted reports.
+fn message(s: String) -> String {
+    s.replace(['\u{2068}', '\u{2069}'], "")
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.1}s", d.as_secs_f64())
+}
+
+fn status_of(r: &RunRecord) -> String {
+    match &r.failure {
+        Some(f) => message(msg::report_failed(f.message.clone())),
+        None => message(msg::report_passed()),
+    }
+}
+
+// the reclaimed bytes or the changed lock entries, whatever the run changed.
+fn changes_of(r: &RunRecord) -> Vec<String> {
+    let mut changes = vec![];
+    if let Some(b) = r.reclaimed_bytes {
+        changes.push(message(msg::report_reclaimed(b)));
+    }
+    changes.extend(r.lock_changes.iter().map(|a| a.to_string()));
+    changes
+}
+
+fn header_cells() -> [String; 6] {
+    [
+        msg::report_project(),
+        msg::report_action(),
+        msg::report_toolchain(),
+        msg::report_status(),
+        msg::report_duration(),
+        msg::report_changes(),
+    ].map(message)
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// A table of the runs, then the stderr tails of the failures.
+pub fn render_markdown(records: &[RunRecord]) -> String {
+    let failures = records.iter().filter(|a| a.failure.is_some()).count();
+    let mut out = String::new();
+    out.push_str(&format!("## {}\n\n", message(msg::report_title())));
+    out.push_str(&format!("{}\n\n", message(msg::report_summary(records.len(), failures))));
+    out.push_str(&format!("| {} |\n", header_cells().join(" | ")));
+    out.push_str("|---|---|---|---|--:|---|\n");
+    for r in records {
+        out.push_str(&format!("| `{}` | `{}` | {} | {} | {} | {} |\n",
+            markdown_cell(&r.project.to_string_lossy()),
+            markdown_cell(&r.action),
+            markdown_cell(&message(r.toolchain_description.clone())),
+            markdown_cell(&status_of(r)),
+            format_duration(r.duration),
+            changes_of(r).iter().map(|a| markdown_cell(a)).collect::<Vec<_>>().join("<br>")));
+    }
+
+    if failures > 0 {
+        out.push_str(&format!("\n### {}\n", message(msg::report_failures())));
+        for r in records {
+            let Some(f) = &r.failure else { continue; };
+            out.push_str(&format!("\n#### `{}`\n\n", r.project.to_string_lossy()));
+            // an error of this program has no output, only its message.
+            if f.stderr.is_empty() {
+                out.push_str(&format!("{}\n", f.message));
+                continue;
+            }
+            // a fence longer than any backtick run in the output.
+            let tail = strip_ansi(&f.stderr_tail());
+            let fence = "`".repeat(tail.split(|a| a != '`').map(|a| a.len()).max().unwrap_or(0).max(2) + 1);
+            out.push_str(&format!("{fence}\n{tail}\n{fence}\n"));
+        }
+    }
+    out
+}
+
+/// A page without any external resource, so it can be published as it is.
+pub fn render_html(records: &[RunRecord]) -> String {
+    let failures = records.iter().filter(|a| a.failure.is_some()).count();
+    let title = xml_escape(&message(msg::report_title()));
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", title));
+    out.push_str("<style>\n\
+        body { font-family: sans-serif; margin: 2em; }\n\
+        table { border-collapse: collapse; }\n\
+        th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }\n\
+        tr.failed td { background: #fdecea; }\n\
+        tr.passed td { background: #edf7ed; }\n\
+        pre { margin: 0.3em 0; white-space: pre-wrap; }\n\
+        </style>\n</head>\n<body>\n");
+    out.push_str(&format!("<h1>{}</h1>\n", title));
+    out.push_str(&format!("<p>{}</p>\n", xml_escape(&message(msg::report_summary(records.len(), failures)))));
+    out.push_str("<table>\n<tr>");
+    for h in header_cells() {
+        out.push_str(&format!("<th>{}</th>", xml_escape(&h)));
+    }
+    out.push_str("</tr>\n");
+    for r in records {
+        out.push_str(&format!("<tr class=\"{}\">", if r.failure.is_some() { "failed" } else { "passed" }));
+        out.push_str(&format!("<td><code>{}</code></td>", xml_escape(&r.project.to_string_lossy())));
+        out.push_str(&format!("<td><code>{}</code></td>", xml_escape(&r.action)));
+        out.push_str(&format!("<td>{}</td>", xml_escape(&message(r.toolchain_description.clone()))));
+        out.push_str(&format!("<td>{}", xml_escape(&status_of(r))));
+        if let Some(f) = r.failure.as_ref().filter(|a| !a.stderr.is_empty()) {
+            out.push_str(&format!("<details><summary>stderr</summary><pre>{}</pre></details>",
+                xml_escape(&strip_ansi(&f.stderr_tail()))));
+        }
+        out.push_str("</td>");
+        out.push_str(&format!("<td>{}</td>", format_duration(r.duration)));
+        out.push_str(&format!("<td>{}</td>", changes_of(r).iter().map(|a| xml_escape(a)).collect::<Vec<_>>().join("<br>")));
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
+
+pub fn render_report(records: &[RunRecord], format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Junit => render_junit(records),
+        ReportFormat::Markdown => render_markdown(records),
+        ReportFormat::Html => render_html(records),
+    }
+}
+
+/// Writes the report to the file, or to stdout without one.
+pub fn write_report(records: &[RunRecord], format: ReportFormat, output: Option<&Path>) -> Result<()> {
+    let report = render_report(records, format);
+    match output {
+        Some(p) => {
+            fs::write(p, report)
+                .context(WriteFileSnafu {
+                    dynamic_errmsg: msg::write_file_failed(p.to_string_lossy().into_owned()),
+                })
+        },
+        None => {
+            print!("{}", report);
+            Ok(())
+        }
+    }
+}
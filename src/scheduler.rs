@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+use crate::dep_graph::DependencyGraph;
+
+struct SchedulerState {
+    // the count of unfinished dependencies of every project
+    remaining: Vec<usize>,
+    ready: BTreeSet<usize>,
+    running: usize,
+    finished: usize,
+}
+
+/// Runs `action` on every project of the graph with up to `jobs` threads,
+/// a project starts only after all of its dependencies have finished.
+/// The results are returned in the order the projects finished.
+pub fn run_in_dependency_order<T, F>(graph: &DependencyGraph, jobs: usize, action: F) -> Vec<(usize, T)>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+{
+    let remaining = graph.dependencies.iter()
+        .map(|a| a.len())
+        .collect::<Vec<_>>();
+    let ready = remaining.iter()
+        .enumerate()
+        .filter(|(_, n)| **n == 0)
+        .map(|(i, _)| i)
+        .collect();
+    let state = Mutex::new(SchedulerState {
+        remaining,
+        ready,
+        running: 0,
+        finished: 0,
+    });
+    let wakeup = Condvar::new();
+    let results = Mutex::new(Vec::with_capacity(graph.projects.len()));
+    let total = graph.projects.len();
+
+    thread::scope(|s| {
+        for _ in 0..jobs.max(1).min(total.max(1)) {
+            s.spawn(|| {
+                loop {
+                    let next = {
+                        let mut st = state.lock().expect("Scheduler mutex poisoned.");
+                        loop {
+                            if let Some(i) = st.ready.pop_first() {
+                                st.running += 1;
+                                break Some(i);
+                            }
+                            // nothing runs and nothing is ready: finished, or the rest are in a cycle.
+                            if st.finished == total || st.running == 0 {
+                                break None;
+                            }
+                            st = wakeup.wait(st).expect("Scheduler mutex poisoned.");
+                        }
+                    };
+                    let Some(i) = next else {
+                        wakeup.notify_all();
+                        return;
+                    };
+
+                    let r = action(i);
+                    results.lock().expect("Scheduler mutex poisoned.").push((i, r));
+
+                    let mut st = state.lock().expect("Scheduler mutex poisoned.");
+                    st.running -= 1;
+                    st.finished += 1;
+                    for &d in &graph.dependents[i] {
+                        st.remaining[d] -= 1;
+                        if st.remaining[d] == 0 {
+                            st.ready.insert(d);
+                        }
+                    }
+                    wakeup.notify_all();
+                }
+            });
+        }
+    });
+
+    results.into_inner().expect("Scheduler mutex poisoned.")
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use dialoguer::MultiSelect;
+use dialoguer::console::Term;
+use snafu::prelude::*;
+
+use crate::disk_usage::{self, DiskUsage};
+use crate::errors::*;
+use crate::messages as msg;
+
+fn describe_age(last_modified: Option<SystemTime>) -> String {
+    let Some(elapsed) = last_modified
+        .and_then(|a| SystemTime::now().duration_since(a).ok()) else {
+        return msg::age_unknown();
+    };
+    let minutes = elapsed.as_secs() / 60;
+    if minutes < 60 {
+        msg::age_minutes(minutes)
+    } else if minutes < 60 * 24 {
+        msg::age_hours(minutes / 60)
+    } else {
+        msg::age_days(minutes / 60 / 24)
+    }
+}
+
+fn describe_project(project: &Path, usage: &DiskUsage) -> String {
+    msg::selection_item(
+        project.to_string_lossy().into_owned(),
+        usage.bytes,
+        describe_age(usage.last_modified),
+    )
+}
+
+/// Parses `1 3 5-7`, `2,4` or `all` into zero based indexes, `None` on a malformed answer.
+fn parse_numbered_answer(answer: &str, count: usize) -> Option<BTreeSet<usize>> {
+    let mut selected = BTreeSet::new();
+    for token in answer.split(|a: char| a.is_whitespace() || a == ',')
+        .filter(|a| !a.is_empty()) {
+        if token == "all" || token == "*" {
+            selected.extend(0..count);
+            continue;
+        }
+        let (start, end) = match token.split_once('-') {
+            Some((s, e)) => (s.parse::<usize>().ok()?, e.parse::<usize>().ok()?),
+            None => {
+                let n = token.parse::<usize>().ok()?;
+                (n, n)
+            }
+        };
+        if start == 0 || end > count || start > end {
+            return None;
+        }
+        selected.extend(start - 1..end);
+    }
+    Some(selected)
+}
+
+fn select_with_numbered_prompt(items: &[String]) -> Result<BTreeSet<usize>> {
+    let stdin = io::stdin();
+    let mut stderr = io::stderr();
+    for (i, item) in items.iter().enumerate() {
+        let _ = writeln!(stderr, "{:>4}) {}", i + 1, item);
+    }
+
+    loop {
+        let _ = write!(stderr, "{} ", msg::selection_numbered_prompt());
+        let _ = stderr.flush();
+
+        let mut answer = String::new();
+        let read = stdin.lock()
+            .read_line(&mut answer)
+            .context(StdIOSnafu)?;
+        if read == 0 {
+            // EOF, nothing selected.
+            return Ok(BTreeSet::new());
+        }
+        match parse_numbered_answer(answer.trim(), items.len()) {
+            Some(selected) => return Ok(selected),
+            None => {
+                let _ = writeln!(stderr, "{}", msg::selection_invalid_answer(answer.trim().to_owned()));
+            }
+        }
+    }
+}
+
+/// Lets the user pick among the discovered projects, with a checklist on a terminal
+/// and a numbered prompt on stdin otherwise. The prompts are written to stderr
+/// so the generated commands on stdout keep clean.
+pub fn select_projects(projects: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    if projects.is_empty() {
+        return Ok(projects);
+    }
+    let items = projects.iter()
+        .map(|a| describe_project(a, &disk_usage::disk_usage(a)))
+        .collect::<Vec<_>>();
+
+    let selected = if io::stdin().is_terminal() && io::stderr().is_terminal() {
+        MultiSelect::new()
+            .with_prompt(msg::selection_checklist_prompt())
+            .items(&items)
+            .interact_on_opt(&Term::stderr())
+            .context(SelectionPromptSnafu)?
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+    } else {
+        select_with_numbered_prompt(&items)?
+    };
+
+    Ok(projects.into_iter()
+        .enumerate()
+        .filter(|(i, _)| selected.contains(i))
+        .map(|(_, a)| a)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(answer: &str, count: usize) -> Option<Vec<usize>> {
+        parse_numbered_answer(answer, count).map(|a| a.into_iter().collect())
+    }
+
+    #[test]
+    fn parse_numbered_answer_lists_and_ranges() {
+        assert_eq!(selected("1 3 5-7", 8), Some(vec![0, 2, 4, 5, 6]));
+        assert_eq!(selected("2,4, 4", 4), Some(vec![1, 3]));
+        assert_eq!(selected("all", 3), Some(vec![0, 1, 2]));
+        assert_eq!(selected("* 2", 2), Some(vec![0, 1]));
+        assert_eq!(selected("3-3", 3), Some(vec![2]));
+        // nothing selected.
+        assert_eq!(selected("  ", 3), Some(vec![]));
+    }
+
+    #[test]
+    fn parse_numbered_answer_rejects_the_malformed() {
+        for answer in ["0", "4", "2-4", "3-1", "a", "1-", "-2", "1.5"] {
+            assert_eq!(selected(answer, 3), None, "{answer}");
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Command;
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap_complete::Shell;
+use snafu::prelude::*;
+
+use crate::errors::*;
+use crate::messages as msg;
+
+/// The shells having a directory the completions are loaded from.
+pub static INSTALLED_SHELLS: [Shell; 3] = [Shell::Bash, Shell::Zsh, Shell::Fish];
+
+// the completions only offer the names of the values,
+// the aliases like `--gt direct` are made values of their own.
+fn with_value_aliases(cmd: Command) -> Command {
+    cmd.mut_args(|a| {
+        let values = a.get_possible_values();
+        if values.iter().all(|v| v.get_name_and_aliases().count() == 1) {
+            return a;
+        }
+        let values = values.iter()
+            .flat_map(|v| v.get_name_and_aliases().map(move |name| {
+                let value = PossibleValue::new(name.to_owned()).hide(v.is_hide_set());
+                match v.get_help() {
+                    Some(help) => value.help(help.clone()),
+                    None => value,
+                }
+            }))
+            .collect::<Vec<_>>();
+        a.value_parser(PossibleValuesParser::new(values))
+    })
+    .mut_subcommands(with_value_aliases)
+}
+
+/// The completion script of `cmd` for the shell, built from its localized help.
+pub fn render_completions(cmd: Command, shell: Shell) -> Vec<u8> {
+    let mut cmd = with_value_aliases(cmd);
+    let bin_name = cmd.get_name().to_owned();
+    let mut out = vec![];
+    clap_complete::generate(shell, &mut cmd, bin_name, &mut out);
+    out
+}
+
+/// The man page of `cmd` in roff, the sections clap_mangen names itself are renamed
+/// after the messages, the others take the headings of the help.
+pub fn render_man(cmd: Command) -> Result<Vec<u8>> {
+    let mut out = vec![];
+    clap_mangen::Man::new(cmd)
+        .render(&mut out)
+        .context(StdIOSnafu)?;
+
+    let sections = [
+        ("NAME", msg::man_section_name()),
+        ("SYNOPSIS", msg::man_section_synopsis()),
+        ("DESCRIPTION", msg::man_section_description()),
+        ("VERSION", msg::man_section_version()),
+        ("AUTHORS", msg::man_section_authors()),
+    ];
+    let page = String::from_utf8_lossy(&out)
+        .lines()
+        .map(|line| {
+            let heading = line.strip_prefix(".SH ")
+                .and_then(|a| sections.iter().find(|(name, _)| *name == a));
+            match heading {
+                // quoted like roff does, when the heading has several words.
+                Some((_, localized)) if localized.contains(char::is_whitespace) => {
+                    format!(".SH \"{}\"\n", localized.replace('"', "\"\""))
+                },
+                Some((_, localized)) => format!(".SH {localized}\n"),
+                None => format!("{line}\n"),
+            }
+        })
+        .collect::<String>();
+    Ok(page.into_bytes())
+}
+
+/// Prints a script or a page generated here, for the `completions` and the `man` commands.
+pub fn write_stdout(content: &[u8]) -> Result<()> {
+    match io::stdout().lock().write_all(content) {
+        // the reader went away, like `head`.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        a => a.context(StdIOSnafu),
+    }
+}
+
+/// Where the shell looks for the completions under a data directory like `/usr/share`,
+/// `None` for the shells without such a directory.
+pub fn completions_path(data_dir: &Path, bin_name: &str, shell: Shell) -> Option<PathBuf> {
+    match shell {
+        Shell::Bash => Some(data_dir.join("bash-completion").join("completions").join(bin_name)),
+        Shell::Zsh => Some(data_dir.join("zsh").join("site-functions").join(format!("_{bin_name}"))),
+        Shell::Fish => Some(data_dir.join("fish").join("vendor_completions.d").join(format!("{bin_name}.fish"))),
+        _ => None,
+    }
+}
+
+pub fn man_path(data_dir: &Path, bin_name: &str) -> PathBuf {
+    data_dir.join("man").join("man1").join(format!("{bin_name}.1"))
+}
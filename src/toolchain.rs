@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::messages as msg;
+
+// rustup prefers the legacy `rust-toolchain` when both exist.
+static TOOLCHAIN_FILE_NAMES: [&str; 2] = ["rust-toolchain", "rust-toolchain.toml"];
+
+#[derive(Debug, Clone)]
+pub struct ToolchainFile {
+    pub path: PathBuf,
+    pub channel: Option<String>,
+}
+
+fn parse_toolchain_file(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    match content.parse::<toml::Table>() {
+        Ok(t) => {
+            t.get("toolchain")
+                .and_then(|a| a.get("channel").or_else(|| a.get("path")))
+                .and_then(|a| a.as_str())
+                .map(|a| a.to_owned())
+        },
+        Err(_) => {
+            // the legacy `rust-toolchain` holds only the channel name.
+            let channel = content.trim();
+            if channel.is_empty() || channel.contains('\n') {
+                None
+            } else {
+                Some(channel.to_owned())
+            }
+        }
+    }
+}
+
+/// Finds the toolchain file rustup would use for the project, searching
+/// the project dir and its parents like rustup does.
+pub fn detect_toolchain_file(project: &Path) -> Option<ToolchainFile> {
+    for dir in project.ancestors() {
+        for name in TOOLCHAIN_FILE_NAMES {
+            let path = dir.join(name);
+            if path.is_file() {
+                let channel = parse_toolchain_file(&path);
+                return Some(ToolchainFile { path, channel });
+            }
+        }
+    }
+    None
+}
+
+/// The toolchain a run on the project would use, for the report in the outputs.
+pub fn describe_toolchain(project: &Path, explicit: Option<&str>) -> String {
+    let file = detect_toolchain_file(project);
+    let file_channel = file.as_ref()
+        .map(|a| a.channel.clone().unwrap_or_else(|| "?".to_owned()));
+    let file_path = file.as_ref()
+        .map(|a| a.path.to_string_lossy().into_owned());
+
+    match (explicit, file_channel, file_path) {
+        (Some(t), Some(fc), Some(fp)) => {
+            msg::toolchain_overridden(t.to_owned(), fc, fp)
+        },
+        (Some(t), _, _) => {
+            msg::toolchain_explicit(t.to_owned())
+        },
+        (None, Some(fc), Some(fp)) => {
+            msg::toolchain_from_file(fc, fp)
+        },
+        _ => {
+            msg::toolchain_default()
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::manifest;
+use crate::messages as msg;
+
+/// Why a discovered `Cargo.toml` is left out of the runs.
+#[derive(Debug, Clone)]
+pub enum ManifestProblem {
+    Unreadable { reason: String },
+    // nothing but whitespace and comments
+    Empty,
+    // a `{{project-name}}` of cargo-generate, the templates are parsed fine at times
+    Placeholder { line: usize, column: usize, text: String, placeholder: String },
+    Syntax { line: usize, column: usize, text: String, reason: String },
+    // valid TOML, but cargo needs one of them
+    NoPackageOrWorkspace,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidManifest {
+    pub project: PathBuf,
+    pub problem: ManifestProblem,
+}
+
+impl InvalidManifest {
+    pub fn manifest_path(&self) -> PathBuf {
+        manifest::manifest_path(&self.project)
+    }
+}
+
+// 1-based, the column counted in characters like the editors do.
+fn line_column(content: &str, offset: usize) -> (usize, usize, String) {
+    let offset = offset.min(content.len());
+    let before = &content[..content.floor_char_boundary(offset)];
+    let line_start = before.rfind('\n').map_or(0, |a| a + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    let text = content[line_start..]
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+    (line, column, text)
+}
+
+// the liquid tags of cargo-generate, `{{ ... }}` and `{% ... %}`.
+fn find_placeholder(content: &str) -> Option<(usize, String)> {
+    for (open, close) in [("{{", "}}"), ("{%", "%}")] {
+        let mut from = 0;
+        while let Some(start) = content[from..].find(open).map(|a| a + from) {
+            let rest = &content[start..];
+            let line_end = rest.find('\n').unwrap_or(rest.len());
+            if let Some(end) = rest[..line_end].find(close) {
+                return Some((start, rest[..end + close.len()].to_owned()));
+            }
+            from = start + open.len();
+        }
+    }
+    None
+}
+
+// the placeholders cargo can't take, in the name or the version of the package. In the
+// other strings, like the replacements of cargo-release, `{{version}}` is a plain text.
+fn package_placeholder(content: &str, table: &toml::Table) -> Option<(usize, String)> {
+    let package = table.get("package")?;
+    for key in ["name", "version"] {
+        let Some(value) = package.get(key).and_then(|a| a.as_str()) else { continue; };
+        if let Some((start, placeholder)) = find_placeholder(value) {
+            // the value as written, the placeholder is pointed at on its line.
+            let offset = content.find(value).map_or(0, |a| a + start);
+            return Some((offset, placeholder));
+        }
+    }
+    None
+}
+
+fn check_content(content: &str) -> Option<ManifestProblem> {
+    let is_empty = content.lines()
+        .map(|a| a.trim())
+        .all(|a| a.is_empty() || a.starts_with('#'));
+    if is_empty {
+        return Some(ManifestProblem::Empty);
+    }
+    let placeholder_problem = |(offset, placeholder)| {
+        let (line, column, text) = line_column(content, offset);
+        ManifestProblem::Placeholder { line, column, text, placeholder }
+    };
+    match content.parse::<toml::Table>() {
+        Ok(t) => {
+            if let Some(p) = package_placeholder(content, &t) {
+                return Some(placeholder_problem(p));
+            }
+            if t.contains_key("package") || t.contains_key("workspace") {
+                None
+            } else {
+                Some(ManifestProblem::NoPackageOrWorkspace)
+            }
+        },
+        // a template is told from a broken file by its tags.
+        Err(e) => match find_placeholder(content) {
+            Some(p) => Some(placeholder_problem(p)),
+            None => {
+                let offset = e.span().map_or(0, |a| a.start);
+                let (line, column, text) = line_column(content, offset);
+                Some(ManifestProblem::Syntax { line, column, text, reason: e.message().trim().to_owned() })
+            }
+        }
+    }
+}
+
+/// Parses the manifest of the project, `None` when cargo could read it.
+pub fn check_manifest(project: &Path) -> Option<ManifestProblem> {
+    match fs::read_to_string(manifest::manifest_path(project)) {
+        Ok(o) => check_content(&o),
+        Err(e) => Some(ManifestProblem::Unreadable { reason: e.to_string() }),
+    }
+}
+
+/// Splits the discovered projects into the ones with a manifest cargo can read,
+/// in their order, and the ones left out.
+pub fn partition_manifests(projects: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<InvalidManifest>) {
+    let mut valid = vec![];
+    let mut invalid = vec![];
+    for project in projects {
+        match check_manifest(&project) {
+            None => valid.push(project),
+            Some(problem) => invalid.push(InvalidManifest { project, problem }),
+        }
+    }
+    (valid, invalid)
+}
+
+pub fn describe_problem(problem: &ManifestProblem) -> String {
+    match problem {
+        ManifestProblem::Unreadable { reason } => msg::manifest_problem_unreadable(reason.clone()),
+        ManifestProblem::Empty => msg::manifest_problem_empty(),
+        ManifestProblem::Placeholder { placeholder, .. } => msg::manifest_problem_placeholder(placeholder.clone()),
+        ManifestProblem::Syntax { reason, .. } => msg::manifest_problem_syntax(reason.clone()),
+        ManifestProblem::NoPackageOrWorkspace => msg::manifest_problem_no_package(),
+    }
+}
+
+/// One line on stderr for every project left out of the run.
+pub fn report_skipped(invalid: &[InvalidManifest]) {
+    for a in invalid {
+        eprintln!("{}", msg::manifest_skipped(
+            a.manifest_path().to_string_lossy().into_owned(),
+            describe_problem(&a.problem)));
+    }
+}
+
+/// `validate`, the invalid manifests as `path:line:column: problem` with the line
+/// pointed at, for the editors and the CI logs. Returns whether one is found.
+pub fn run_validate(discovered: usize, invalid: &[InvalidManifest]) -> bool {
+    for a in invalid {
+        let path = a.manifest_path().to_string_lossy().into_owned();
+        let problem = describe_problem(&a.problem);
+        match &a.problem {
+            ManifestProblem::Placeholder { line, column, text, .. }
+            | ManifestProblem::Syntax { line, column, text, .. } => {
+                println!("{path}:{line}:{column}: {problem}");
+                println!("  | {text}");
+                // the tabs are kept so the caret lines up.
+                let indent = text.chars()
+                    .take(column - 1)
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect::<String>();
+                println!("  | {indent}^");
+            },
+            _ => {
+                println!("{path}: {problem}");
+            }
+        }
+    }
+    println!("{}", msg::validate_summary(discovered, invalid.len()));
+    !invalid.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_column_counts_characters() {
+        let content = "[package]\nname = \"é{{x}}\"\n";
+        let offset = content.find("{{").unwrap();
+        assert_eq!(line_column(content, offset), (2, 10, "name = \"é{{x}}\"".to_owned()));
+        assert_eq!(line_column(content, 0), (1, 1, "[package]".to_owned()));
+        // past the end, and in the middle of a character.
+        assert_eq!(line_column(content, 1000).0, 3);
+        assert_eq!(line_column(content, offset - 1), (2, 9, "name = \"é{{x}}\"".to_owned()));
+    }
+
+    #[test]
+    fn find_placeholder_on_one_line() {
+        assert_eq!(find_placeholder("name = \"{{project-name}}\""), Some((8, "{{project-name}}".to_owned())));
+        assert_eq!(find_placeholder("{% if x %}\n"), Some((0, "{% if x %}".to_owned())));
+        assert_eq!(find_placeholder("a = \"{{\"\nb = \"}}\""), None);
+        assert_eq!(find_placeholder("{{a}} {{b}}"), Some((0, "{{a}}".to_owned())));
+        assert_eq!(find_placeholder("[package]\nname = \"a\""), None);
+    }
+
+    #[test]
+    fn placeholders_in_strings_are_kept() {
+        let content = "[package]\nname = \"a\"\nversion = \"0.1.0\"\n\n\
+            [package.metadata.release]\n\
+            pre-release-replacements = [{ file = \"README.md\", search = \"a = .*\", replace = \"a = {{version}}\" }]\n";
+        assert!(check_content(content).is_none());
+    }
+
+    #[test]
+    fn placeholders_of_the_templates() {
+        let content = "[package]\nname = \"{{project-name}}\"\nversion = \"0.1.0\"\n";
+        match check_content(content) {
+            Some(ManifestProblem::Placeholder { line, column, placeholder, .. }) => {
+                assert_eq!((line, column, placeholder.as_str()), (2, 9, "{{project-name}}"));
+            },
+            p => panic!("{:?}", p),
+        }
+        // not parsed as TOML.
+        let content = "[package]\nname = \"a\"\n{% if x %}\nedition = \"2021\"\n{% endif %}\n";
+        assert!(matches!(check_content(content), Some(ManifestProblem::Placeholder { line: 3, .. })));
+    }
+
+    #[test]
+    fn other_problems() {
+        assert!(matches!(check_content("# a comment\n\n"), Some(ManifestProblem::Empty)));
+        assert!(matches!(check_content("[dependencies]\n"), Some(ManifestProblem::NoPackageOrWorkspace)));
+        assert!(matches!(check_content("[package\n"), Some(ManifestProblem::Syntax { line: 1, .. })));
+        assert!(check_content("[workspace]\nmembers = []\n").is_none());
+    }
+}